@@ -0,0 +1,85 @@
+//! Repeated timing for `--bench`, built on `std::time` alone.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Spread of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<12}{:<12}{:.3?}",
+            format!("{:.3?}", self.min),
+            format!("{:.3?}", self.median),
+            self.max
+        )
+    }
+}
+
+/// Calls `f` `runs` times (at least once), returning its last result and the timings.
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, Stats) {
+    let mut samples: Vec<Duration> = Vec::new();
+    let mut last: Option<T> = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = f();
+        samples.push(start.elapsed());
+        last = Some(result);
+    }
+    (last.unwrap(), Stats::from_samples(&mut samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_millis(*x)).collect()
+    }
+
+    #[test]
+    fn summarises_odd_and_even_counts() {
+        let stats = Stats::from_samples(&mut ms(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+
+        let stats = Stats::from_samples(&mut ms(&[4, 1, 2, 8]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn measures_every_run() {
+        let mut calls = 0;
+        let (last, _) = measure(4, || {
+            calls += 1;
+            calls
+        });
+        assert_eq!(last, 4);
+    }
+}
@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt;
+
+/// A parse failure located by line and column in the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending text; empty when the input ended early.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `token`, which must be a slice of `input`.
+    pub fn at(input: &str, token: &str, message: impl Into<String>) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(start)
+            .min(input.len());
+        debug_assert!(
+            offset + token.len() <= input.len(),
+            "token is not a slice of the input"
+        );
+
+        let bytes = &input.as_bytes()[..offset];
+        let line_start = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let line = bytes.iter().filter(|b| **b == b'\n').count() + 1;
+        let column = match std::str::from_utf8(&bytes[line_start..]) {
+            Ok(prefix) => prefix.chars().count() + 1,
+            Err(_) => offset - line_start + 1,
+        };
+        ParseError {
+            line,
+            column,
+            text: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds an error for input that stops before the parser was done.
+    pub fn at_end(input: &str, message: impl Into<String>) -> ParseError {
+        let trimmed = input.trim_end();
+        ParseError::at(input, &trimmed[trimmed.len()..], message)
+    }
+
+    /// Formats the error with the offending line and a caret marker under it.
+    pub fn render(&self, source: &str, input: &str) -> String {
+        let mut out = format!("error: {}\n", self.describe());
+        let gutter = " ".repeat(self.line.to_string().len());
+        out.push_str(&format!(
+            "{}--> {}:{}:{}\n",
+            gutter, source, self.line, self.column
+        ));
+        if let Some(line) = input.lines().nth(self.line - 1) {
+            let width = self.text.lines().next().unwrap_or("").chars().count();
+            out.push_str(&format!("{} |\n", gutter));
+            out.push_str(&format!(
+                "{} | {}\n",
+                self.line,
+                line.trim_end_matches('\r')
+            ));
+            out.push_str(&format!(
+                "{} | {}{}\n",
+                gutter,
+                " ".repeat(self.column - 1),
+                "^".repeat(width.max(1))
+            ));
+        }
+        out
+    }
+
+    fn describe(&self) -> String {
+        if self.text.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{}: {:?}", self.message, self.text)
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.describe())
+    }
+}
+
+impl Error for ParseError {}
+
+/// A well-formed input the solver still can't answer, such as a tie the
+/// puzzle rules don't break.
+///
+/// Any error type converts into it, so a part can return its own error with
+/// `?` or `.into()` and callers can get it back with [`SolveError::downcast_ref`].
+#[derive(Debug)]
+pub struct SolveError(Box<dyn Error + Send + Sync>);
+
+impl SolveError {
+    /// An error carrying only a message.
+    pub fn new(message: impl Into<String>) -> SolveError {
+        SolveError(message.into().into())
+    }
+
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<E> for SolveError {
+    fn from(err: E) -> SolveError {
+        SolveError(Box::new(err))
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_token() {
+        let input = "199\n200\n  2x8\n";
+        let token = &input[10..13];
+        let err = ParseError::at(input, token, "Invalid integer");
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.text, "2x8");
+        assert_eq!(err.to_string(), "3:3: Invalid integer: \"2x8\"");
+    }
+
+    #[test]
+    fn locates_end_of_input() {
+        let input = "7,4,9\n\n22 13\n\n";
+        let err = ParseError::at_end(input, "Unexpected end of input");
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(err.to_string(), "3:6: Unexpected end of input");
+    }
+
+    #[test]
+    fn renders_caret() {
+        let input = "forward 5\nsideways 3\n";
+        let err = ParseError::at(input, &input[10..18], "Invalid direction");
+        assert_eq!(
+            err.render("example.txt", input),
+            "error: Invalid direction: \"sideways\"\n \
+             --> example.txt:2:1\n  |\n2 | sideways 3\n  | ^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn wraps_solve_errors() {
+        let err = SolveError::from(ParseError::at_end("", "No data"));
+        assert_eq!(err.to_string(), "1:1: No data");
+        assert!(err.downcast_ref::<ParseError>().is_some());
+        assert_eq!(SolveError::new("No winner").to_string(), "No winner");
+    }
+}
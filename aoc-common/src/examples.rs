@@ -0,0 +1,144 @@
+//! Checks a day's solver against the answers manifest kept next to its examples.
+//!
+//! Each day directory holds an `answers.txt` with one line per input file:
+//!
+//! ```text
+//! # file part1 part2
+//! example.txt 7 5
+//! ```
+//!
+//! A `-` leaves that part unchecked, e.g. for multi-line answers or a part the
+//! input wasn't written for.
+
+use crate::error::ParseError;
+use crate::input::{lines, read_file};
+use crate::solution::Solution;
+use std::fs;
+use std::path::Path;
+
+/// Expected answers for one input file.
+#[derive(Debug, PartialEq)]
+pub struct Expected {
+    pub file: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+fn answer(value: &str) -> Option<String> {
+    match value {
+        "-" => None,
+        _ => Some(value.to_string()),
+    }
+}
+
+/// Parses an answers manifest, skipping `#` comment lines.
+pub fn parse_manifest(input: &str) -> Result<Vec<Expected>, ParseError> {
+    let mut out: Vec<Expected> = Vec::new();
+    for line in lines(input) {
+        if line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 3 {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Expected \"<file> <part1> <part2>\"",
+            ));
+        }
+        out.push(Expected {
+            file: fields[0].to_string(),
+            part1: answer(fields[1]),
+            part2: answer(fields[2]),
+        });
+    }
+    Ok(out)
+}
+
+/// Runs `S` on every file listed in `dir/answers.txt` and panics listing each mismatch.
+///
+/// Also fails when an `example*.txt` in `dir` is missing from the manifest.
+pub fn check<S: Solution>(dir: &str) {
+    let dir = Path::new(dir);
+    let manifest_path = dir.join("answers.txt");
+    let manifest_raw = read_file(manifest_path.to_str().unwrap());
+    let manifest = match parse_manifest(&manifest_raw) {
+        Ok(manifest) => manifest,
+        Err(err) => panic!("{}", err.render("answers.txt", &manifest_raw)),
+    };
+
+    let mut failures: Vec<String> = Vec::new();
+    for entry in fs::read_dir(dir).expect("Failed to list day directory") {
+        let name = entry.unwrap().file_name().to_string_lossy().to_string();
+        if name.starts_with("example")
+            && name.ends_with(".txt")
+            && !manifest.iter().any(|e| e.file == name)
+        {
+            failures.push(format!("{}: missing from answers.txt", name));
+        }
+    }
+
+    for expected in manifest.iter() {
+        let raw = read_file(dir.join(&expected.file).to_str().unwrap());
+        let input = match S::parse(&raw) {
+            Ok(input) => input,
+            Err(err) => {
+                failures.push(err.render(&expected.file, &raw));
+                continue;
+            }
+        };
+        if let Some(want) = &expected.part1 {
+            match S::part1(&input) {
+                Ok(got) if got == *want => (),
+                Ok(got) => failures.push(format!(
+                    "{} part 1: expected {}, got {}",
+                    expected.file, want, got
+                )),
+                Err(err) => failures.push(format!("{} part 1: error: {}", expected.file, err)),
+            }
+        }
+        if let Some(want) = &expected.part2 {
+            match S::part2(&input) {
+                Some(Ok(got)) if got == *want => (),
+                Some(Ok(got)) => failures.push(format!(
+                    "{} part 2: expected {}, got {}",
+                    expected.file, want, got
+                )),
+                Some(Err(err)) => {
+                    failures.push(format!("{} part 2: error: {}", expected.file, err))
+                }
+                None => failures.push(format!("{} part 2: not implemented", expected.file)),
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("day {}:\n{}", S::DAY, failures.join("\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_manifest() {
+        let input = "# file part1 part2\nexample.txt 7 5\nsmall.txt 39 -\n";
+        assert_eq!(
+            parse_manifest(input),
+            Ok(vec![
+                Expected {
+                    file: "example.txt".to_string(),
+                    part1: Some("7".to_string()),
+                    part2: Some("5".to_string()),
+                },
+                Expected {
+                    file: "small.txt".to_string(),
+                    part1: Some("39".to_string()),
+                    part2: None,
+                },
+            ])
+        );
+        assert!(parse_manifest("example.txt 7\n").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! A rectangular grid with flat row-major storage.
+//!
+//! Positions are `(row, col)` pairs. A grid can optionally wrap around its
+//! edges, in which case every offset lands back inside it.
+
+use crate::error::ParseError;
+use crate::input::digit_grid;
+use std::ops::{Index, IndexMut};
+
+/// `(row, col)` offsets of the four orthogonal neighbors.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// `(row, col)` offsets of all eight surrounding cells, in row-major order.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    wrapping: bool,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Grid rows differ in length"
+        );
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+            wrapping: false,
+        }
+    }
+
+    /// Builds a `height` x `width` grid by calling `f` on each position in row-major order.
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut((usize, usize)) -> T) -> Grid<T> {
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                cells.push(f((row, col)));
+            }
+        }
+        Grid {
+            width,
+            height,
+            cells,
+            wrapping: false,
+        }
+    }
+
+    /// Makes offsets and neighbors wrap around the edges (a torus).
+    pub fn with_wrapping(mut self, wrapping: bool) -> Grid<T> {
+        self.wrapping = wrapping;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if pos.0 < self.height && pos.1 < self.width {
+            Some(&self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if pos.0 < self.height && pos.1 < self.width {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// The position `delta` away from `pos`.
+    ///
+    /// `pos` itself may lie outside the grid. Without wrapping the result is
+    /// `None` when it falls off an edge; with wrapping it is always `Some`.
+    pub fn offset(&self, pos: (usize, usize), delta: (isize, isize)) -> Option<(usize, usize)> {
+        let row = pos.0 as isize + delta.0;
+        let col = pos.1 as isize + delta.1;
+        if self.wrapping {
+            if self.height == 0 || self.width == 0 {
+                return None;
+            }
+            Some((
+                row.rem_euclid(self.height as isize) as usize,
+                col.rem_euclid(self.width as isize) as usize,
+            ))
+        } else if row >= 0
+            && col >= 0
+            && (row as usize) < self.height
+            && (col as usize) < self.width
+        {
+            Some((row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    /// The orthogonal neighbors of `pos` that lie on the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos` that lie on the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        SURROUNDING
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// A grid of the same shape holding `f` of each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            wrapping: self.wrapping,
+        }
+    }
+
+    /// Draws the grid one character per cell, each row ending in a newline.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(&f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a block of single-digit cells, as accepted by [`digit_grid`].
+    pub fn parse_digits(input: &str) -> Result<Grid<u8>, ParseError> {
+        Ok(Grid::from_rows(digit_grid(input)?))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos).expect("Position outside the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        self.get_mut(pos).expect("Position outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_renders_digits() {
+        let grid = Grid::parse_digits("123\n456\n").unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(
+            grid.render(|x| char::from_digit(*x as u32, 10).unwrap()),
+            "123\n456\n"
+        );
+        assert!(Grid::parse_digits("12\n3\n").is_err());
+    }
+
+    #[test]
+    fn neighbors_stop_at_edges() {
+        let grid = Grid::from_fn(3, 3, |_| 0);
+        let corner: Vec<(usize, usize)> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn wrapping_neighbors() {
+        let grid = Grid::from_fn(3, 4, |_| 0).with_wrapping(true);
+        let corner: Vec<(usize, usize)> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(2, 0), (0, 3), (0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 8);
+        assert_eq!(grid.offset((2, 3), (1, 1)), Some((0, 0)));
+    }
+
+    #[test]
+    fn offsets_from_outside() {
+        let grid = Grid::from_fn(2, 2, |(row, col)| row * 2 + col);
+        assert_eq!(grid.offset((2, 2), (-1, -1)), Some((1, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.map(|x| x * 10)[(1, 1)], 30);
+    }
+}
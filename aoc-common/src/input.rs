@@ -0,0 +1,164 @@
+use crate::error::ParseError;
+use std::fs;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+/// Reads all of stdin into a string.
+pub fn read_stdin() -> String {
+    let mut buffer = String::new();
+    io::stdin()
+        .read_to_string(&mut buffer)
+        .expect("Failed to read stdin");
+    buffer
+}
+
+/// Reads a whole file into a string.
+pub fn read_file(filename: &str) -> String {
+    fs::read_to_string(filename).expect("Failed to read file")
+}
+
+/// Iterates over the trimmed, non-blank lines of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+}
+
+/// Iterates over every line of the input with only the line ending removed.
+///
+/// Use this instead of `lines` when leading whitespace or blank lines matter.
+pub fn raw_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(|line| line.trim_end_matches('\r'))
+}
+
+/// Parses `token`, a slice of `input`, reporting `message` at its position on failure.
+pub fn parse_token<T: FromStr>(input: &str, token: &str, message: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(input, token, message))
+}
+
+/// Parses each non-blank line as a single value.
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    lines(input)
+        .map(|line| parse_token(input, line, "Invalid value"))
+        .collect()
+}
+
+/// Parses every comma-separated value in the input, across all lines.
+pub fn comma_separated<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    lines(input)
+        .flat_map(|line| line.split(','))
+        .map(|val| parse_token(input, val.trim(), "Invalid value"))
+        .collect()
+}
+
+/// Parses a non-empty rectangular block of single decimal digits, one row per line.
+pub fn digit_grid(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut grid: Vec<Vec<u8>> = Vec::new();
+    for line in lines(input) {
+        let mut row: Vec<u8> = Vec::new();
+        for (i, c) in line.char_indices() {
+            match c.to_digit(10) {
+                Some(digit) => row.push(digit as u8),
+                None => {
+                    let token = &line[i..i + c.len_utf8()];
+                    return Err(ParseError::at(input, token, "Invalid digit"));
+                }
+            }
+        }
+        if !grid.is_empty() && row.len() != grid[0].len() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Row length differs from the first row",
+            ));
+        }
+        grid.push(row);
+    }
+    if grid.is_empty() {
+        return Err(ParseError::at_end(input, "Empty grid"));
+    }
+    Ok(grid)
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+///
+/// Each section keeps its interior line breaks; empty sections are dropped.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut out: Vec<&str> = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end: usize = 0;
+    let mut offset: usize = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start {
+                out.push(&input[s..end]);
+                start = None;
+            }
+        } else {
+            if start.is_none() {
+                start = Some(offset);
+            }
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        out.push(&input[s..end]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_skips_blanks_and_trims() {
+        let got: Vec<&str> = lines("  1\n\n2  \r\n3").collect();
+        assert_eq!(got, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn raw_lines_keeps_leading_whitespace() {
+        let got: Vec<&str> = raw_lines("#.#\r\n  #A#\n").collect();
+        assert_eq!(got, vec!["#.#", "  #A#"]);
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(parse_lines::<i32>("199\n200\n-3\n"), Ok(vec![199, 200, -3]));
+        let err = parse_lines::<i32>("199\n\n 2x0\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 2, "2x0"));
+    }
+
+    #[test]
+    fn parses_comma_separated() {
+        assert_eq!(
+            comma_separated::<u8>("3,4,3\n1,2\n"),
+            Ok(vec![3, 4, 3, 1, 2])
+        );
+        let err = comma_separated::<u8>("3,4,3\n1,,2\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, ""));
+    }
+
+    #[test]
+    fn parses_digit_grid() {
+        assert_eq!(
+            digit_grid("123\n456\n"),
+            Ok(vec![vec![1, 2, 3], vec![4, 5, 6]])
+        );
+        let err = digit_grid("123\n4a6\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "a"));
+        assert!(digit_grid("123\n45\n").is_err());
+        assert!(digit_grid("\n").is_err());
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "7,4,9\n\n22 13\n 8  2\n\n\n3 15\n";
+        assert_eq!(sections(input), vec!["7,4,9", "22 13\n 8  2", "3 15"]);
+    }
+}
@@ -0,0 +1,19 @@
+//! Shared helpers for the daily puzzle crates.
+
+pub mod bench;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod rng;
+pub mod runner;
+pub mod search;
+pub mod solution;
+
+pub use error::{ParseError, SolveError};
+pub use grid::Grid;
+pub use input::{
+    comma_separated, digit_grid, lines, parse_lines, parse_token, raw_lines, read_file, read_stdin,
+    sections,
+};
+pub use solution::{Answer, Solution};
@@ -0,0 +1,500 @@
+use crate::bench::{measure, Stats};
+use crate::input::read_stdin;
+use crate::solution::{Answer, Solution};
+use std::env;
+use std::fs;
+use std::process;
+
+pub const USAGE: &str = "[--part N] [--input FILE] [--bench RUNS] [--format text|json]";
+
+/// How answers are written to stdout.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One `{day, part, answer, elapsed_ms}` object per line.
+    Json,
+}
+
+/// Command line options shared by every day.
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    /// Only run this part; both parts run when unset.
+    pub part: Option<u8>,
+    /// Read the puzzle input from this file instead of stdin.
+    pub input: Option<String>,
+    /// Repeat parsing and each part this many times and report timings.
+    pub bench: Option<usize>,
+    pub format: Format,
+}
+
+impl Options {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => {
+                    let value = args.next().ok_or("--part needs a value")?;
+                    let part: u8 = match value.as_str() {
+                        "1" => 1,
+                        "2" => 2,
+                        _ => return Err(format!("Invalid part: {}", value)),
+                    };
+                    options.part = Some(part);
+                }
+                "--input" => {
+                    let value = args.next().ok_or("--input needs a value")?;
+                    options.input = Some(value);
+                }
+                "--bench" => {
+                    let value = args.next().ok_or("--bench needs a run count")?;
+                    let runs: usize = match value.parse() {
+                        Ok(runs) if runs > 0 => runs,
+                        _ => return Err(format!("Invalid run count: {}", value)),
+                    };
+                    options.bench = Some(runs);
+                }
+                "--format" => {
+                    let value = args.next().ok_or("--format needs a value")?;
+                    options.format = match value.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        _ => return Err(format!("Invalid format: {}", value)),
+                    };
+                }
+                _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+                _ => {
+                    // A bare argument is taken as the input file.
+                    if options.input.is_some() {
+                        return Err(format!("Unexpected argument: {}", arg));
+                    }
+                    options.input = Some(arg);
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    fn wants(&self, part: u8) -> bool {
+        match self.part {
+            None => true,
+            Some(p) => p == part,
+        }
+    }
+}
+
+fn print_answer(part: u8, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {}:", part);
+        println!("{}", answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+/// Quotes `value` as a JSON string.
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_answer(day: u8, part: u8, answer: Option<&str>, stats: &Stats) -> String {
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{}}}",
+        day,
+        part,
+        answer.map_or("null".to_string(), json_string),
+        stats.median.as_secs_f64() * 1000.0
+    )
+}
+
+fn timing_table(runs: usize, timings: &[(&str, Stats)]) -> String {
+    let mut out = format!("\nTiming over {} run(s):\n", runs);
+    out.push_str(&format!("{:<8}{:<12}{:<12}max\n", "", "min", "median"));
+    for (name, stats) in timings {
+        out.push_str(&format!("{:<8}{}\n", name, stats));
+    }
+    out
+}
+
+/// Reads the file named by `options`, or stdin, along with a name for diagnostics.
+pub fn read_input(options: &Options) -> Result<(&str, String), String> {
+    match &options.input {
+        Some(filename) => match fs::read_to_string(filename) {
+            Ok(raw) => Ok((filename.as_str(), raw)),
+            Err(err) => Err(format!("error: {}: {}", filename, err)),
+        },
+        None => Ok(("<stdin>", read_stdin())),
+    }
+}
+
+/// Reads the input named by `options`, solves the requested parts and prints them.
+///
+/// With `--bench`, parsing and each part are repeated and their timings
+/// reported after the answers; in JSON mode that table goes to stderr so
+/// stdout stays machine-readable. Unreadable or malformed input, or a part
+/// that fails to solve, comes back as a diagnostic ready to print; answers
+/// from the other part are still printed first.
+pub fn run<S: Solution>(options: &Options) -> Result<(), String> {
+    run_with::<S>(options, |_| {})
+}
+
+/// Like [`run`], calling `inspect` once on the parsed input before solving.
+///
+/// `inspect` runs outside the timings, so days can dump their input there
+/// without slowing down `--bench`.
+pub fn run_with<S: Solution>(
+    options: &Options,
+    inspect: impl FnOnce(&S::Input),
+) -> Result<(), String> {
+    let (source, raw) = read_input(options)?;
+    let runs = options.bench.unwrap_or(1);
+    let (parsed, parse_time) = measure(runs, || S::parse(&raw));
+    let input = parsed.map_err(|err| err.render(source, &raw))?;
+    inspect(&input);
+    let mut results: Vec<(u8, Option<Answer>, Stats)> = Vec::new();
+    if options.wants(1) {
+        let (answer, stats) = measure(runs, || S::part1(&input));
+        results.push((1, Some(answer), stats));
+    }
+    if options.wants(2) {
+        let (answer, stats) = measure(runs, || S::part2(&input));
+        results.push((2, answer, stats));
+    }
+
+    let mut timings: Vec<(&str, Stats)> = vec![("parse", parse_time)];
+    let mut failures: Vec<String> = Vec::new();
+    for (part, answer, stats) in results.iter() {
+        let answer = match answer {
+            Some(Ok(answer)) => Some(answer.as_str()),
+            Some(Err(err)) => {
+                failures.push(format!("error: part {}: {}", part, err));
+                continue;
+            }
+            None => None,
+        };
+        match options.format {
+            Format::Text => match answer {
+                Some(answer) => print_answer(*part, answer),
+                None => println!("Part {}: not implemented", part),
+            },
+            Format::Json => println!("{}", json_answer(S::DAY, *part, answer, stats)),
+        }
+        if answer.is_some() {
+            timings.push((if *part == 1 { "part 1" } else { "part 2" }, *stats));
+        }
+    }
+    if options.bench.is_some() {
+        let table = timing_table(runs, &timings);
+        match options.format {
+            Format::Text => print!("{}", table),
+            Format::Json => eprint!("{}", table),
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Prints a diagnostic from `run` and exits with a failure status.
+pub fn fail(diagnostic: &str) -> ! {
+    eprint!("{}", diagnostic);
+    if !diagnostic.ends_with('\n') {
+        eprintln!();
+    }
+    process::exit(1);
+}
+
+/// Entry point for a day's binary that only prints the answers.
+///
+/// Days with flags of their own use [`ExtraArgs`] instead.
+pub fn main<S: Solution>() {
+    ExtraArgs::from_env::<S>("").run_answers::<S>();
+}
+
+/// Removes `flag` and the argument after it from `args`.
+fn remove_flag(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|x| x == flag) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err(format!("{} needs a value", flag));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+/// Parses the options left for a mode that prints something other than the
+/// answers, rejecting the ones that only make sense for answers.
+fn mode_options(args: Vec<String>) -> Result<Options, String> {
+    let options = Options::parse(args.into_iter())?;
+    let mut unused: Vec<&str> = Vec::new();
+    if options.part.is_some() {
+        unused.push("--part");
+    }
+    if options.bench.is_some() {
+        unused.push("--bench");
+    }
+    if options.format != Format::Text {
+        unused.push("--format");
+    }
+    if unused.is_empty() {
+        Ok(options)
+    } else {
+        let verb = if unused.len() == 1 {
+            "applies"
+        } else {
+            "apply"
+        };
+        Err(format!(
+            "{} only {} to the answers",
+            unused.join(", "),
+            verb
+        ))
+    }
+}
+
+/// Command line of a day binary with modes besides printing the answers.
+///
+/// The day's own flags are taken out first and the rest is parsed as the
+/// shared [`Options`]. Any bad argument prints the usage line and exits with
+/// status 2.
+pub struct ExtraArgs {
+    day: u8,
+    /// Usage of the day's own flags, shown before [`USAGE`].
+    usage: &'static str,
+    args: Vec<String>,
+}
+
+impl ExtraArgs {
+    /// The process arguments for day `S`, whose own flags are described by `usage`.
+    pub fn from_env<S: Solution>(usage: &'static str) -> ExtraArgs {
+        ExtraArgs {
+            day: S::DAY,
+            usage,
+            args: env::args().skip(1).collect(),
+        }
+    }
+
+    /// Removes `flag`, returning whether it was given.
+    pub fn take_switch(&mut self, flag: &str) -> bool {
+        let before = self.args.len();
+        self.args.retain(|x| x != flag);
+        self.args.len() != before
+    }
+
+    /// Removes `flag` and returns its value, if it was given.
+    pub fn take_flag(&mut self, flag: &str) -> Option<String> {
+        remove_flag(&mut self.args, flag).unwrap_or_else(|message| self.usage_error(&message))
+    }
+
+    /// Like [`ExtraArgs::take_flag`], converting the value with `parse`.
+    pub fn take_parsed<T>(
+        &mut self,
+        flag: &str,
+        parse: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Option<T> {
+        let value = self.take_flag(flag)?;
+        Some(parse(&value).unwrap_or_else(|message| self.usage_error(&message)))
+    }
+
+    /// Prints `message` and the usage line, then exits with status 2.
+    pub fn usage_error(&self, message: &str) -> ! {
+        eprintln!("{}", message);
+        if self.usage.is_empty() {
+            eprintln!("Usage: day{} {}", self.day, USAGE);
+        } else {
+            eprintln!("Usage: day{} {} {}", self.day, self.usage, USAGE);
+        }
+        process::exit(2);
+    }
+
+    /// Solves and prints the answers, as [`main`] does.
+    pub fn run_answers<S: Solution>(self) {
+        self.run_answers_with::<S>(|_| {});
+    }
+
+    /// Like [`ExtraArgs::run_answers`], calling `inspect` on the parsed input
+    /// as [`run_with`] does.
+    pub fn run_answers_with<S: Solution>(self, inspect: impl FnOnce(&S::Input)) {
+        match Options::parse(self.args.iter().cloned()) {
+            Ok(options) => {
+                if let Err(diagnostic) = run_with::<S>(&options, inspect) {
+                    fail(&diagnostic);
+                }
+            }
+            Err(message) => self.usage_error(&message),
+        }
+    }
+
+    /// Runs `mode` on the remaining options instead of printing the answers.
+    ///
+    /// `--part`, `--bench` and `--format` are rejected, since the modes
+    /// print their own output.
+    pub fn run_mode(self, mode: impl FnOnce(&Options) -> Result<(), String>) {
+        match mode_options(self.args.clone()) {
+            Ok(options) => {
+                if let Err(diagnostic) = mode(&options) {
+                    fail(&diagnostic);
+                }
+            }
+            Err(message) => self.usage_error(&message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parses_options() {
+        let options = Options::parse(args(&[
+            "--part",
+            "2",
+            "--input",
+            "example.txt",
+            "--bench",
+            "10",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options,
+            Options {
+                part: Some(2),
+                input: Some("example.txt".to_string()),
+                bench: Some(10),
+                format: Format::Text,
+            }
+        );
+    }
+
+    #[test]
+    fn bare_argument_is_input() {
+        let options = Options::parse(args(&["example1.txt"])).unwrap();
+        assert_eq!(options.input, Some("example1.txt".to_string()));
+        assert_eq!(options.part, None);
+    }
+
+    #[test]
+    fn formats_json_answers() {
+        let stats = Stats {
+            min: Duration::from_micros(1500),
+            median: Duration::from_micros(2500),
+            max: Duration::from_micros(4000),
+        };
+        assert_eq!(
+            json_answer(13, 2, Some("#.\n\"x\""), &stats),
+            r##"{"day":13,"part":2,"answer":"#.\n\"x\"","elapsed_ms":2.5}"##
+        );
+        assert_eq!(
+            json_answer(8, 2, None, &stats),
+            r#"{"day":8,"part":2,"answer":null,"elapsed_ms":2.5}"#
+        );
+    }
+
+    struct Tied;
+
+    impl Solution for Tied {
+        const DAY: u8 = 3;
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<(), crate::ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Answer {
+            Ok("1".to_string())
+        }
+
+        fn part2(_input: &()) -> Option<Answer> {
+            Some(Err(crate::SolveError::new("rating is ambiguous")))
+        }
+    }
+
+    #[test]
+    fn solve_errors_fail_the_run() {
+        let options = Options {
+            input: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").to_string()),
+            ..Options::default()
+        };
+        assert_eq!(
+            run::<Tied>(&options),
+            Err("error: part 2: rating is ambiguous".to_string())
+        );
+        let options = Options {
+            part: Some(1),
+            ..options
+        };
+        assert_eq!(run::<Tied>(&options), Ok(()));
+    }
+
+    #[test]
+    fn inspects_input_once() {
+        let options = Options {
+            part: Some(1),
+            input: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").to_string()),
+            bench: Some(3),
+            ..Options::default()
+        };
+        let mut calls = 0;
+        assert_eq!(run_with::<Tied>(&options, |_| calls += 1), Ok(()));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn takes_extra_flags() {
+        let mut rest: Vec<String> = args(&["--trace", "aimed", "in.txt", "--seed"]).collect();
+        assert_eq!(
+            remove_flag(&mut rest, "--trace"),
+            Ok(Some("aimed".to_string()))
+        );
+        assert_eq!(remove_flag(&mut rest, "--emit"), Ok(None));
+        assert!(remove_flag(&mut rest, "--seed").is_err());
+        assert_eq!(rest, vec!["in.txt", "--seed"]);
+
+        assert!(mode_options(rest).is_err());
+        let options = mode_options(args(&["in.txt"]).collect()).unwrap();
+        assert_eq!(options.input, Some("in.txt".to_string()));
+        assert_eq!(
+            mode_options(args(&["--format", "json", "--part", "1"]).collect()),
+            Err("--part, --format only apply to the answers".to_string())
+        );
+        assert!(mode_options(args(&["--format", "text"]).collect()).is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        assert!(Options::parse(args(&["--part", "3"])).is_err());
+        assert!(Options::parse(args(&["--part"])).is_err());
+        assert!(Options::parse(args(&["--verbose"])).is_err());
+        assert!(Options::parse(args(&["--bench", "0"])).is_err());
+        assert!(Options::parse(args(&["--bench", "many"])).is_err());
+        assert!(Options::parse(args(&["--format", "yaml"])).is_err());
+        assert!(Options::parse(args(&["a.txt", "b.txt"])).is_err());
+    }
+}
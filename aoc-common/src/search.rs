@@ -0,0 +1,144 @@
+//! Shortest paths over implicit graphs: Dijkstra, or A* given a heuristic.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// A cheapest route found by [`shortest_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    /// Total cost of every step along `states`.
+    pub cost: usize,
+    /// Every state visited, from the start to the goal inclusive.
+    pub states: Vec<S>,
+}
+
+/// Finds the cheapest path from `start` to any state satisfying `goal`.
+///
+/// `successors` yields each neighbor of a state together with the cost of
+/// moving there. `heuristic`, when given, must never overestimate the
+/// remaining cost, turning the search into A*; `None` is plain Dijkstra.
+/// A state already expanded is expanded again if a cheaper path to it turns
+/// up, which only happens when the heuristic is not also consistent.
+/// Returns `None` when no goal is reachable.
+pub fn shortest_path<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: Option<&mut dyn FnMut(&S) -> usize>,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut estimate = |state: &S| heuristic.as_mut().map_or(0, |h| h(state));
+
+    // States are numbered in discovery order; the heap and parent links use the numbers.
+    let mut ids: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = Vec::new();
+    let mut distances: Vec<usize> = Vec::new();
+    let mut parents: Vec<Option<usize>> = Vec::new();
+    let mut visited: Vec<bool> = Vec::new();
+    let mut q: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+
+    q.push(Reverse((estimate(&start), 0)));
+    ids.insert(start.clone(), 0);
+    states.push(start);
+    distances.push(0);
+    parents.push(None);
+    visited.push(false);
+
+    while let Some(Reverse((_, current))) = q.pop() {
+        if visited[current] {
+            continue;
+        }
+        if goal(&states[current]) {
+            return Some(Path {
+                cost: distances[current],
+                states: trace(&states, &parents, current),
+            });
+        }
+        visited[current] = true;
+
+        for (neighbor, cost) in successors(&states[current]) {
+            let possible = distances[current] + cost;
+            let id = match ids.get(&neighbor) {
+                Some(&id) if distances[id] <= possible => continue,
+                Some(&id) => id,
+                None => {
+                    let id = states.len();
+                    ids.insert(neighbor.clone(), id);
+                    states.push(neighbor);
+                    distances.push(possible);
+                    parents.push(None);
+                    visited.push(false);
+                    id
+                }
+            };
+            distances[id] = possible;
+            parents[id] = Some(current);
+            visited[id] = false;
+            q.push(Reverse((possible + estimate(&states[id]), id)));
+        }
+    }
+    None
+}
+
+fn trace<S: Clone>(states: &[S], parents: &[Option<usize>], end: usize) -> Vec<S> {
+    let mut path: Vec<S> = vec![states[end].clone()];
+    let mut current = end;
+    while let Some(parent) = parents[current] {
+        path.push(states[parent].clone());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -1-> 1 -1-> 2 -1-> 3, plus a 0 -5-> 3 shortcut that costs more.
+    fn edges(node: &u32) -> Vec<(u32, usize)> {
+        match node {
+            0 => vec![(3, 5), (1, 1)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let path = shortest_path(0, edges, |n| *n == 3, None).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.states, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn uses_heuristic() {
+        let mut remaining = |n: &u32| 3 - (*n as usize).min(3);
+        let path = shortest_path(0, edges, |n| *n == 3, Some(&mut remaining)).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(shortest_path(0, edges, |n| *n == 4, None), None);
+        assert_eq!(shortest_path(0, edges, |n| *n == 0, None).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn reopens_states_for_inconsistent_heuristics() {
+        // 0 -1-> 1 -1-> 3 and 0 -2-> 2 -1-> 3, then 3 -3-> 4. The heuristic
+        // never overestimates but makes 2 look better than 1, so 3 is first
+        // reached the expensive way.
+        let edges = |node: &u32| match node {
+            0 => vec![(1, 1), (2, 2)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 3)],
+            _ => vec![],
+        };
+        let mut remaining = |n: &u32| if *n == 1 { 4 } else { 0 };
+        let path = shortest_path(0, edges, |n| *n == 4, Some(&mut remaining)).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states, vec![0, 1, 3, 4]);
+    }
+}
@@ -0,0 +1,20 @@
+use crate::error::{ParseError, SolveError};
+
+/// A part's answer, or why the input has none.
+pub type Answer = Result<String, SolveError>;
+
+/// A single day's puzzle, split into parsing and the two parts.
+pub trait Solution {
+    /// Day of the advent calendar this solves.
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    /// Returns `None` for days whose second part isn't implemented.
+    fn part2(input: &Self::Input) -> Option<Answer>;
+}
@@ -0,0 +1,65 @@
+use aoc_common::runner::{self, Options};
+use std::env;
+use std::process;
+
+fn usage() -> ! {
+    eprintln!("Usage: aoc run <day> {}", runner::USAGE);
+    process::exit(2);
+}
+
+fn run_day(day: u8, options: &Options) -> Result<(), String> {
+    match day {
+        1 => runner::run::<day1::Day1>(options),
+        2 => runner::run::<day2::Day2>(options),
+        3 => runner::run::<day3::Day3>(options),
+        4 => runner::run::<day4::Day4>(options),
+        5 => runner::run::<day5::Day5>(options),
+        6 => runner::run::<day6::Day6>(options),
+        7 => runner::run::<day7::Day7>(options),
+        8 => runner::run::<day8::Day8>(options),
+        9 => runner::run::<day9::Day9>(options),
+        10 => runner::run::<day10::Day10>(options),
+        11 => runner::run::<day11::Day11>(options),
+        12 => runner::run::<day12::Day12>(options),
+        13 => runner::run::<day13::Day13>(options),
+        14 => runner::run::<day14::Day14>(options),
+        15 => runner::run::<day15::Day15>(options),
+        16 => runner::run::<day16::Day16>(options),
+        17 => runner::run::<day17::Day17>(options),
+        18 => runner::run::<day18::Day18>(options),
+        19 => runner::run::<day19::Day19>(options),
+        20 => runner::run::<day20::Day20>(options),
+        21 => runner::run::<day21::Day21>(options),
+        22 => runner::run::<day22::Day22>(options),
+        23 => runner::run::<day23::Day23>(options),
+        24 => runner::run::<day24::Day24>(options),
+        25 => runner::run::<day25::Day25>(options),
+        _ => {
+            eprintln!("No solution for day {}", day);
+            process::exit(2);
+        }
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => (),
+        _ => usage(),
+    }
+    let day: u8 = match args.next().map(|x| x.parse::<u8>()) {
+        Some(Ok(day)) => day,
+        _ => usage(),
+    };
+    match Options::parse(args) {
+        Ok(options) => {
+            if let Err(diagnostic) = run_day(day, &options) {
+                runner::fail(&diagnostic);
+            }
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            usage();
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Runs every day's solver on the inputs listed in its `answers.txt`.
+
+macro_rules! examples {
+    ($($(#[$attr:meta])* $day:ident: $solution:path,)*) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $day() {
+                aoc_common::examples::check::<$solution>(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/../",
+                    stringify!($day)
+                ));
+            }
+        )*
+    };
+}
+
+examples! {
+    day1: day1::Day1,
+    day2: day2::Day2,
+    day3: day3::Day3,
+    day4: day4::Day4,
+    day5: day5::Day5,
+    day6: day6::Day6,
+    day7: day7::Day7,
+    day8: day8::Day8,
+    day9: day9::Day9,
+    day10: day10::Day10,
+    day11: day11::Day11,
+    day12: day12::Day12,
+    day13: day13::Day13,
+    day14: day14::Day14,
+    day15: day15::Day15,
+    day16: day16::Day16,
+    day17: day17::Day17,
+    day18: day18::Day18,
+    day19: day19::Day19,
+    day20: day20::Day20,
+    day21: day21::Day21,
+    day22: day22::Day22,
+    day23: day23::Day23,
+    day24: day24::Day24,
+    day25: day25::Day25,
+}
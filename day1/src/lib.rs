@@ -0,0 +1,256 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead};
+
+fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    aoc_common::parse_lines(input)
+}
+
+#[cfg(test)]
+fn number_increases(values: &[i32]) -> usize {
+    let mut increases: usize = 0;
+    let mut prev: i32 = 0;
+    let mut first = true;
+    for &x in values {
+        if !first && x > prev {
+            increases += 1;
+        }
+        first = false;
+        prev = x;
+    }
+    increases
+}
+
+/// Sums of every `window` consecutive values; empty when there are fewer than `window`.
+///
+/// The sums are widened to `i64` so that large readings can't overflow.
+pub fn convolve(values: &[i32], window: usize) -> Vec<i64> {
+    values
+        .windows(window)
+        .map(|w| w.iter().map(|&x| i64::from(x)).sum())
+        .collect()
+}
+
+/// Counts how often the sum of a sliding `window` of readings increases.
+///
+/// Consecutive windows share all but their end readings, so a window sum
+/// grows exactly when the reading entering it beats the one leaving it. Only
+/// the last `window` readings are kept.
+pub fn window_increases(readings: impl IntoIterator<Item = i32>, window: usize) -> usize {
+    assert!(window > 0, "Window must hold at least one reading");
+    let mut recent: VecDeque<i32> = VecDeque::with_capacity(window);
+    let mut increases: usize = 0;
+    for x in readings {
+        if recent.len() == window {
+            let leaving = recent.pop_front().expect("Window is full");
+            if x > leaving {
+                increases += 1;
+            }
+        }
+        recent.push_back(x);
+    }
+    increases
+}
+
+/// Like [`window_increases`], reading one value per line from `reader` without loading it all.
+///
+/// Malformed readings come back as `InvalidData` errors wrapping a [`ParseError`].
+pub fn stream_window_increases(reader: impl BufRead, window: usize) -> io::Result<usize> {
+    let mut failure: Option<io::Error> = None;
+    let mut lines = reader.lines().enumerate();
+    let readings = std::iter::from_fn(|| loop {
+        let (i, line) = lines.next()?;
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                failure = Some(err);
+                return None;
+            }
+        };
+        let token = line.trim();
+        if token.is_empty() {
+            continue;
+        }
+        match token.parse::<i32>() {
+            Ok(x) => return Some(x),
+            Err(_) => {
+                let mut err = ParseError::at(&line, token, "Invalid value");
+                err.line = i + 1;
+                failure = Some(io::Error::new(io::ErrorKind::InvalidData, err));
+                return None;
+            }
+        }
+    });
+    let increases = window_increases(readings, window);
+    match failure {
+        Some(err) => Err(err),
+        None => Ok(increases),
+    }
+}
+
+/// Step-by-step statistics over a series of depth readings.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DepthReport {
+    pub increases: usize,
+    pub decreases: usize,
+    pub equal: usize,
+    /// Inclusive `(start, end)` indices of the first longest strictly increasing run.
+    pub longest_run: Option<(usize, usize)>,
+    /// The index of the reading after the largest absolute change, and that change.
+    pub largest_jump: Option<(usize, i64)>,
+}
+
+impl DepthReport {
+    /// Builds the report for readings or window sums; changes are computed in `i64`.
+    pub fn new<T: Copy + Into<i64>>(values: &[T]) -> DepthReport {
+        let mut report = DepthReport::default();
+        if values.is_empty() {
+            return report;
+        }
+        report.longest_run = Some((0, 0));
+        let mut run_start = 0;
+        for i in 1..values.len() {
+            let jump = values[i].into() - values[i - 1].into();
+            match jump.cmp(&0) {
+                Ordering::Greater => report.increases += 1,
+                Ordering::Less => report.decreases += 1,
+                Ordering::Equal => report.equal += 1,
+            }
+            if jump <= 0 {
+                run_start = i;
+            }
+            let (start, end) = report.longest_run.expect("Set for non-empty input");
+            if i - run_start > end - start {
+                report.longest_run = Some((run_start, i));
+            }
+            if report
+                .largest_jump
+                .is_none_or(|(_, largest)| jump.unsigned_abs() > largest.unsigned_abs())
+            {
+                report.largest_jump = Some((i, jump));
+            }
+        }
+        report
+    }
+}
+
+impl fmt::Display for DepthReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Increases: {}", self.increases)?;
+        writeln!(f, "Decreases: {}", self.decreases)?;
+        writeln!(f, "Equal: {}", self.equal)?;
+        match self.longest_run {
+            Some((start, end)) => writeln!(
+                f,
+                "Longest increasing run: {} readings, {}..={}",
+                end - start + 1,
+                start,
+                end
+            )?,
+            None => writeln!(f, "Longest increasing run: none")?,
+        }
+        match self.largest_jump {
+            Some((at, jump)) => write!(f, "Largest jump: {:+} at {}", jump, at),
+            None => write!(f, "Largest jump: none"),
+        }
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(window_increases(input.iter().copied(), 1).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(window_increases(input.iter().copied(), 3).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn matches_reference_for_any_window() {
+        let values = parse_input(EXAMPLE).unwrap();
+        for window in 1..=values.len() + 1 {
+            // The example's sums fit the reference's i32 readings.
+            let sums: Vec<i32> = convolve(&values, window)
+                .into_iter()
+                .map(|sum| i32::try_from(sum).unwrap())
+                .collect();
+            let expected = number_increases(&sums);
+            assert_eq!(window_increases(values.iter().copied(), window), expected);
+            assert_eq!(
+                stream_window_increases(EXAMPLE.as_bytes(), window).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn reports_example() {
+        let values = parse_input(EXAMPLE).unwrap();
+        let report = DepthReport::new(&values);
+        assert_eq!(
+            report,
+            DepthReport {
+                increases: 7,
+                decreases: 2,
+                equal: 0,
+                longest_run: Some((0, 3)),
+                largest_jump: Some((6, 33)),
+            }
+        );
+        assert_eq!(report.increases, number_increases(&values));
+
+        let sums = DepthReport::new(&convolve(&values, 3));
+        assert_eq!((sums.increases, sums.decreases, sums.equal), (5, 1, 1));
+        assert_eq!(sums.longest_run, Some((3, 7)));
+        assert_eq!(sums.largest_jump, Some((5, 69)));
+    }
+
+    #[test]
+    fn reports_extreme_readings() {
+        let values = [i32::MAX, i32::MIN, i32::MAX];
+        let report = DepthReport::new(&values);
+        assert_eq!(report.largest_jump, Some((1, -(1i64 << 32) + 1)));
+        assert_eq!((report.increases, report.decreases), (1, 1));
+        assert_eq!(convolve(&values, 2), vec![-1, -1]);
+        assert_eq!(convolve(&[i32::MAX; 3], 3), vec![3 * i64::from(i32::MAX)]);
+    }
+
+    #[test]
+    fn reports_short_input() {
+        assert_eq!(DepthReport::new::<i32>(&[]), DepthReport::default());
+        let single = DepthReport::new(&[5]);
+        assert_eq!(single.longest_run, Some((0, 0)));
+        assert_eq!(single.largest_jump, None);
+    }
+
+    #[test]
+    fn short_input_has_no_windows() {
+        assert_eq!(window_increases([199, 200], 3), 0);
+        assert_eq!(window_increases([], 3), 0);
+    }
+
+    #[test]
+    fn stream_reports_bad_line() {
+        let err = stream_window_increases("199\n\n  2x0\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "3:3: Invalid value: \"2x0\"");
+    }
+}
@@ -1,50 +1,52 @@
-use std::io;
+use aoc_common::runner::{self, ExtraArgs, Options};
+use aoc_common::Solution;
+use day1::{Day1, DepthReport};
+use std::fs::File;
+use std::io::{self, BufReader};
 
-fn parse_input() -> Vec<i32> {
-    let mut out: Vec<i32> = Vec::new();
-    let mut buffer = String::new();
-    loop {
-        let n = io::stdin()
-            .read_line(&mut buffer)
-            .expect("Failed to read stdin");
-        if n == 0 {
-            // End of input
-            break;
+/// Counts window increases straight from the input without loading it.
+fn stream(window: usize, options: &Options) -> Result<(), String> {
+    let result = match &options.input {
+        Some(filename) => match File::open(filename) {
+            Ok(file) => day1::stream_window_increases(BufReader::new(file), window),
+            Err(err) => return Err(format!("error: {}: {}", filename, err)),
+        },
+        None => day1::stream_window_increases(io::stdin().lock(), window),
+    };
+    match result {
+        Ok(increases) => {
+            println!("Increases: {}", increases);
+            Ok(())
         }
-        let x: i32 = buffer.trim().parse().expect("Invalid integer");
-        out.push(x);
-        buffer.clear();
+        Err(err) => Err(format!("error: {}", err)),
     }
-    out
 }
 
-fn number_increases(values: &[i32]) -> usize {
-    let mut increases: usize = 0;
-    let mut prev: i32 = 0;
-    let mut first = true;
-    for &x in values {
-        if !first && x > prev {
-            increases += 1;
-        }
-        first = false;
-        prev = x;
-    }
-    increases
-}
-
-fn convolve3(values: &[i32]) -> Vec<i32> {
-    let mut out: Vec<i32> = Vec::new();
-    for i in 0..values.len() - 2 {
-        out.push(values[i] + values[i + 1] + values[i + 2]);
-    }
-    out
+/// Prints the depth report for the raw readings and for their three-reading sums.
+fn report(options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let values = Day1::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    println!("Readings:\n{}\n", DepthReport::new(&values));
+    println!(
+        "Three-reading sums:\n{}",
+        DepthReport::new(&day1::convolve(&values, 3))
+    );
+    Ok(())
 }
 
 fn main() {
-    let input = parse_input();
-    let increases = number_increases(&input[..]);
-    println!("Part 1: {}", increases);
-    let windows = convolve3(&input[..]);
-    let window_increases = number_increases(&windows[..]);
-    println!("Part 2: {}", window_increases);
+    // `--stream WINDOW` counts increases for any window size in constant memory;
+    // `--report` prints step statistics instead of the answers.
+    let mut args = ExtraArgs::from_env::<Day1>("[--stream WINDOW | --report]");
+    let report_wanted = args.take_switch("--report");
+    let window = args.take_parsed("--stream", |x| match x.parse() {
+        Ok(window) if window > 0 => Ok(window),
+        _ => Err(format!("Invalid window size: {}", x)),
+    });
+    match (window, report_wanted) {
+        (Some(_), true) => args.usage_error("--stream and --report can't be combined"),
+        (Some(window), false) => args.run_mode(|options| stream(window, options)),
+        (None, true) => args.run_mode(report),
+        (None, false) => args.run_answers::<Day1>(),
+    }
 }
@@ -0,0 +1,152 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::collections::LinkedList;
+
+#[derive(Debug, PartialEq, Clone)]
+enum BraceKind {
+    SQUARE,
+    PAREN,
+    CURLY,
+    ANGLE,
+}
+
+#[derive(Debug)]
+pub struct Brace {
+    kind: BraceKind,
+    open: bool,
+}
+
+impl BraceKind {
+    fn score(&self) -> i64 {
+        match self {
+            BraceKind::SQUARE => 57,
+            BraceKind::PAREN => 3,
+            BraceKind::CURLY => 1197,
+            BraceKind::ANGLE => 25137,
+        }
+    }
+
+    fn autoscore(&self) -> i64 {
+        match self {
+            BraceKind::SQUARE => 2,
+            BraceKind::PAREN => 1,
+            BraceKind::CURLY => 3,
+            BraceKind::ANGLE => 4,
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<Brace>>, ParseError> {
+    let mut out: Vec<Vec<Brace>> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let row: Vec<Brace> = line
+            .char_indices()
+            .map(|(i, c)| match c {
+                '[' => Ok(Brace {
+                    kind: BraceKind::SQUARE,
+                    open: true,
+                }),
+                ']' => Ok(Brace {
+                    kind: BraceKind::SQUARE,
+                    open: false,
+                }),
+                '(' => Ok(Brace {
+                    kind: BraceKind::PAREN,
+                    open: true,
+                }),
+                ')' => Ok(Brace {
+                    kind: BraceKind::PAREN,
+                    open: false,
+                }),
+                '{' => Ok(Brace {
+                    kind: BraceKind::CURLY,
+                    open: true,
+                }),
+                '}' => Ok(Brace {
+                    kind: BraceKind::CURLY,
+                    open: false,
+                }),
+                '<' => Ok(Brace {
+                    kind: BraceKind::ANGLE,
+                    open: true,
+                }),
+                '>' => Ok(Brace {
+                    kind: BraceKind::ANGLE,
+                    open: false,
+                }),
+                _ => Err(ParseError::at(
+                    input,
+                    &line[i..i + c.len_utf8()],
+                    "Invalid character",
+                )),
+            })
+            .collect::<Result<Vec<Brace>, ParseError>>()?;
+        out.push(row);
+    }
+    Ok(out)
+}
+
+fn part1(rows: &[Vec<Brace>]) -> i64 {
+    let mut score = 0;
+    for row in rows.iter() {
+        let mut stack: LinkedList<BraceKind> = LinkedList::new();
+        for brace in row.iter() {
+            if brace.open {
+                stack.push_back(brace.kind.clone());
+            } else {
+                if stack.is_empty() || stack.pop_back().expect("Must not be empty") != brace.kind {
+                    // Invalid
+                    score += brace.kind.score();
+                    break;
+                }
+            }
+        }
+    }
+    score
+}
+
+fn part2(rows: &[Vec<Brace>]) -> i64 {
+    let mut all_scores: Vec<i64> = Vec::new();
+    for row in rows.iter() {
+        let mut stack: LinkedList<BraceKind> = LinkedList::new();
+        let mut valid = true;
+        for brace in row.iter() {
+            if brace.open {
+                stack.push_front(brace.kind.clone());
+            } else {
+                if stack.is_empty() || stack.pop_front().expect("Must not be empty") != brace.kind {
+                    // Invalid
+                    valid = false;
+                    break;
+                }
+            }
+        }
+        if valid {
+            let mut score = 0;
+            while let Some(kind) = stack.pop_front() {
+                score = score * 5 + kind.autoscore();
+            }
+            all_scores.push(score);
+        }
+    }
+    all_scores.sort();
+    all_scores[all_scores.len() / 2]
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Vec<Brace>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(part1(input.as_slice()).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(part2(input.as_slice()).to_string()))
+    }
+}
@@ -0,0 +1,59 @@
+use aoc_common::{Answer, Grid, ParseError, Solution};
+use std::collections::LinkedList;
+
+fn simulate(grid: &mut Grid<u8>) -> usize {
+    let mut ready: LinkedList<(usize, usize)> = LinkedList::new();
+    for pos in grid.positions() {
+        grid[pos] += 1;
+        if grid[pos] == 10 {
+            ready.push_back(pos);
+        }
+    }
+    let mut flashes = 0;
+    while !ready.is_empty() {
+        let pos = ready.pop_front().expect("Must not be empty");
+        grid[pos] = 0;
+        flashes += 1;
+        let neighbors: Vec<(usize, usize)> = grid.neighbors8(pos).collect();
+        for other in neighbors {
+            if grid[other] != 0 && grid[other] != 10 {
+                // Otherwise, already flashed this round
+                grid[other] += 1;
+                if grid[other] == 10 {
+                    ready.push_back(other);
+                }
+            }
+        }
+    }
+    flashes
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_digits(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut grid = input.clone();
+        let mut n_flashes = 0;
+        for _ in 0..100 {
+            n_flashes += simulate(&mut grid);
+        }
+        Ok(n_flashes.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let mut grid = input.clone();
+        let total = grid.width() * grid.height();
+        let mut t = 1;
+        while simulate(&mut grid) != total {
+            t += 1;
+        }
+        Some(Ok(t.to_string()))
+    }
+}
@@ -0,0 +1,155 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::cmp::max;
+use std::collections::HashMap;
+use std::collections::LinkedList;
+
+#[derive(Debug)]
+struct Node {
+    #[allow(dead_code)] // Only read by the debug dump in `main`
+    name: String,
+    big: bool,
+}
+
+#[derive(Debug)]
+pub struct Graph {
+    start: usize,
+    end: usize,
+    nodes: Vec<Node>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn is_big(&self, index: usize) -> bool {
+        self.nodes[index].big
+    }
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        let mut nodes: Vec<Node> = Vec::new();
+        let mut name_to_node: HashMap<String, usize> = HashMap::new();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+
+        for line in aoc_common::lines(input) {
+            let row: Vec<usize> = match line.split_once('-') {
+                Some((a, b)) if !a.is_empty() && !b.is_empty() && !b.contains('-') => [a, b],
+                _ => return Err(ParseError::at(input, line, "Expected an edge like \"A-b\"")),
+            }
+            .into_iter()
+            .map(|part| {
+                if !name_to_node.contains_key(part) {
+                    let big = part.to_uppercase() == part;
+                    nodes.push(Node {
+                        name: part.to_string(),
+                        big,
+                    });
+                    name_to_node.insert(part.to_string(), nodes.len() - 1);
+                }
+                name_to_node[part]
+            })
+            .collect();
+            edges.push((row[0], row[1]));
+        }
+
+        let mut neighbors: Vec<Vec<usize>> = nodes.iter().map(|_| Vec::new()).collect();
+        for (i, j) in edges.iter() {
+            neighbors[*i].push(*j);
+            neighbors[*j].push(*i);
+        }
+
+        let start = match name_to_node.get("start") {
+            Some(index) => *index,
+            None => return Err(ParseError::at_end(input, "Missing \"start\" cave")),
+        };
+        let end = match name_to_node.get("end") {
+            Some(index) => *index,
+            None => return Err(ParseError::at_end(input, "Missing \"end\" cave")),
+        };
+        Ok(Graph {
+            start,
+            end,
+            nodes,
+            neighbors,
+        })
+    }
+}
+
+fn part1(graph: &Graph) -> usize {
+    let mut paths: LinkedList<Vec<usize>> = LinkedList::new();
+    paths.push_back(vec![graph.start]);
+    let mut terminal: usize = 0;
+    while !paths.is_empty() {
+        let path = paths.pop_back().expect("Must not be empty");
+        let last = path[path.len() - 1];
+        if last == graph.end {
+            terminal += 1;
+            continue;
+        }
+        for neighbor in graph.neighbors[last].iter() {
+            if graph.is_big(*neighbor) || !path.contains(neighbor) {
+                let mut new_path = path.clone();
+                new_path.push(*neighbor);
+                paths.push_back(new_path);
+            }
+        }
+    }
+    terminal
+}
+
+fn max_unique_small_cave_visits(graph: &Graph, path: &[usize]) -> usize {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    let mut max_found = 0;
+    for node in path.iter() {
+        if !graph.is_big(*node) {
+            if !counts.contains_key(node) {
+                counts.insert(*node, 0);
+            }
+            counts.insert(*node, counts[node] + 1);
+            max_found = max(counts[node], max_found);
+        }
+    }
+    max_found
+}
+
+fn part2(graph: &Graph) -> usize {
+    let mut paths: LinkedList<Vec<usize>> = LinkedList::new();
+    paths.push_back(vec![graph.start]);
+    let mut terminal: usize = 0;
+    while !paths.is_empty() {
+        let path = paths.pop_back().expect("Must not be empty");
+        let last = path[path.len() - 1];
+        if last == graph.end {
+            terminal += 1;
+            continue;
+        }
+        for neighbor in graph.neighbors[last].iter() {
+            if graph.is_big(*neighbor)
+                || !path.contains(neighbor)
+                || (*neighbor != graph.start
+                    && max_unique_small_cave_visits(graph, path.as_slice()) < 2)
+            {
+                let mut new_path = path.clone();
+                new_path.push(*neighbor);
+                paths.push_back(new_path);
+            }
+        }
+    }
+    terminal
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Graph::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(part1(input).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(part2(input).to_string()))
+    }
+}
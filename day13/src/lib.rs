@@ -0,0 +1,121 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+#[derive(Debug, Copy, Clone)]
+pub enum FoldingInstruction {
+    Y(i32),
+    X(i32),
+}
+
+fn parse(input: &str) -> Result<(HashSet<(i32, i32)>, Vec<FoldingInstruction>), ParseError> {
+    let mut pairs: HashSet<(i32, i32)> = HashSet::new();
+    let mut instructions: Vec<FoldingInstruction> = Vec::new();
+    let sections = aoc_common::sections(input);
+    if sections.len() < 2 {
+        return Err(ParseError::at_end(input, "Missing fold instructions"));
+    }
+    if sections.len() > 2 {
+        return Err(ParseError::at(input, sections[2], "Unexpected section"));
+    }
+    for line in aoc_common::lines(sections[0]) {
+        let parts: Vec<i32> = line
+            .split(',')
+            .map(|val| aoc_common::parse_token(input, val.trim(), "Invalid integer"))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+        if parts.len() != 2 {
+            return Err(ParseError::at(input, line, "Expected a dot like \"6,10\""));
+        }
+        // Convert from x,y to y, x
+        pairs.insert((parts[1], parts[0]));
+    }
+    for line in aoc_common::lines(sections[1]) {
+        // fold along y=7
+        let (axis_name, axis) = match line
+            .strip_prefix("fold along ")
+            .and_then(|rest| rest.split_once('='))
+        {
+            Some(parts) => parts,
+            None => {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "Expected a fold like \"fold along y=7\"",
+                ))
+            }
+        };
+        let axis: i32 = aoc_common::parse_token(input, axis, "Invalid integer")?;
+        instructions.push(match axis_name {
+            "y" => FoldingInstruction::Y(axis),
+            "x" => FoldingInstruction::X(axis),
+            _ => return Err(ParseError::at(input, axis_name, "Invalid axis")),
+        });
+    }
+    Ok((pairs, instructions))
+}
+
+fn fold(pairs: &HashSet<(i32, i32)>, instruction: FoldingInstruction) -> HashSet<(i32, i32)> {
+    let mut out: HashSet<(i32, i32)> = HashSet::new();
+    for (yr, xr) in pairs.iter() {
+        let y = *yr;
+        let x = *xr;
+        match instruction {
+            FoldingInstruction::Y(axis) => {
+                let new_y = if y > axis { 2 * axis - y } else { y };
+                out.insert((new_y, x));
+            }
+            FoldingInstruction::X(axis) => {
+                let new_x = if x > axis { 2 * axis - x } else { x };
+                out.insert((y, new_x));
+            }
+        };
+    }
+    out
+}
+
+fn render(pairs: &HashSet<(i32, i32)>) -> String {
+    let max_y = pairs
+        .iter()
+        .map(|pair| pair.0)
+        .max()
+        .expect("Must be non-empty");
+    let max_x = pairs
+        .iter()
+        .map(|pair| pair.1)
+        .max()
+        .expect("Must be non-empty");
+
+    let mut out = String::new();
+    for y in -1..(max_y + 2) {
+        for x in -1..(max_x + 2) {
+            let key = (y, x);
+            out.push(if pairs.contains(&key) { '#' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = (HashSet<(i32, i32)>, Vec<FoldingInstruction>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (pairs, instructions) = input;
+        Ok(fold(pairs, instructions[0]).len().to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let (pairs, instructions) = input;
+        let mut current_pairs = pairs.clone();
+        for inst in instructions.iter() {
+            current_pairs = fold(&current_pairs, *inst);
+        }
+        Some(Ok(render(&current_pairs)))
+    }
+}
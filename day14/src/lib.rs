@@ -0,0 +1,133 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct PolymerTemplate {
+    unigram_counts: HashMap<char, usize>,
+    bigram_counts: HashMap<(char, char), usize>,
+    rule_tree: HashMap<char, HashMap<char, char>>, // map first to second to middle
+}
+
+impl PolymerTemplate {
+    fn parse(input: &str) -> Result<PolymerTemplate, ParseError> {
+        let mut unigram_counts: HashMap<char, usize> = HashMap::new();
+        let mut bigram_counts: HashMap<(char, char), usize> = HashMap::new();
+        let mut rule_tree: HashMap<char, HashMap<char, char>> = HashMap::new();
+        let sections = aoc_common::sections(input);
+        if sections.len() < 2 {
+            return Err(ParseError::at_end(input, "Missing insertion rules"));
+        }
+        if sections.len() > 2 {
+            return Err(ParseError::at(input, sections[2], "Unexpected section"));
+        }
+        for line in aoc_common::lines(sections[0]) {
+            let mut last: Option<char> = None;
+            for c in line.chars() {
+                *(unigram_counts.entry(c).or_insert(0)) += 1;
+                match last {
+                    None => {}
+                    Some(last_c) => {
+                        *(bigram_counts.entry((last_c, c)).or_insert(0)) += 1;
+                    }
+                }
+                last = Some(c);
+            }
+        }
+        for line in aoc_common::lines(sections[1]) {
+            let parts: Vec<&str> = line.split(" -> ").collect();
+            if parts.len() != 2 {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "Expected a rule like \"CH -> B\"",
+                ));
+            }
+            let pair: Vec<char> = parts[0].chars().collect();
+            let insert: Vec<char> = parts[1].chars().collect();
+            if pair.len() != 2 {
+                return Err(ParseError::at(
+                    input,
+                    parts[0],
+                    "Expected a pair of elements",
+                ));
+            }
+            if insert.len() != 1 {
+                return Err(ParseError::at(input, parts[1], "Expected a single element"));
+            }
+
+            let first = pair[0];
+            let last = pair[1];
+            let mid = insert[0];
+
+            (*rule_tree.entry(first).or_default()).insert(last, mid);
+        }
+        Ok(PolymerTemplate {
+            unigram_counts,
+            bigram_counts,
+            rule_tree,
+        })
+    }
+
+    fn score(&self) -> usize {
+        let most_common = self
+            .unigram_counts
+            .iter()
+            .reduce(|x, y| if x.1 >= y.1 { x } else { y })
+            .unwrap()
+            .1;
+        let least_common = self
+            .unigram_counts
+            .iter()
+            .reduce(|x, y| if x.1 <= y.1 { x } else { y })
+            .unwrap()
+            .1;
+        most_common - least_common
+    }
+
+    fn replacement_step(&mut self) {
+        for (key, count) in self
+            .bigram_counts
+            .iter()
+            .map(|x| (*x.0, *x.1))
+            .collect::<Vec<((char, char), usize)>>()
+            .iter()
+        {
+            let a = key.0;
+            let c = key.1;
+            if self.rule_tree.contains_key(&a) && self.rule_tree[&a].contains_key(&c) {
+                let b = self.rule_tree[&a][&c];
+                *(self.unigram_counts.entry(b).or_insert(0)) += count;
+                *(self.bigram_counts.entry((a, b)).or_insert(0)) += count;
+                *(self.bigram_counts.entry((b, c)).or_insert(0)) += count;
+                *(self.bigram_counts.entry((a, c)).or_insert(0)) -= count;
+            }
+        }
+    }
+}
+
+fn score_after(template: &PolymerTemplate, steps: usize) -> usize {
+    let mut template = template.clone();
+    for _ in 0..steps {
+        template.replacement_step();
+    }
+    template.score()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = PolymerTemplate;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        PolymerTemplate::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(score_after(input, 10).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(score_after(input, 40).to_string()))
+    }
+}
@@ -0,0 +1,64 @@
+use aoc_common::{search, Answer, Grid, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Cave {
+    plot: Grid<usize>,
+}
+
+impl Cave {
+    fn parse(input: &str) -> Result<Cave, ParseError> {
+        let plot = Grid::parse_digits(input)?.map(|x| *x as usize);
+        Ok(Cave { plot })
+    }
+
+    fn times(&self, multiplier: usize) -> Cave {
+        let height = self.plot.height();
+        let width = self.plot.width();
+        let plot = Grid::from_fn(height * multiplier, width * multiplier, |(i, j)| {
+            let orig_val = self.plot[(i % height, j % width)];
+            (orig_val + i / height + j / width - 1) % 9 + 1
+        });
+        Cave { plot }
+    }
+
+    fn heuristic(&self, node: (usize, usize)) -> usize {
+        // Manhattan distance
+        self.plot.height() - 1 - node.0 + self.plot.width() - 1 - node.1
+    }
+
+    fn lowest_risk_path(&self) -> usize {
+        let end = (self.plot.height() - 1, self.plot.width() - 1);
+        let path = search::shortest_path(
+            (0, 0),
+            |node: &(usize, usize)| {
+                self.plot
+                    .neighbors4(*node)
+                    .map(|neighbor| (neighbor, self.plot[neighbor]))
+                    .collect::<Vec<((usize, usize), usize)>>()
+            },
+            |node| *node == end,
+            Some(&mut |node: &(usize, usize)| self.heuristic(*node)),
+        );
+        path.expect("The exit is always reachable").cost
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Cave;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Cave::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(input.lowest_risk_path().to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let big_cave = input.times(5);
+        Some(Ok(big_cave.lowest_risk_path().to_string()))
+    }
+}
@@ -0,0 +1,122 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::cmp;
+
+fn find_highest_touched(x_min: i64, x_max: i64, y_min: i64, y_max: i64) -> (i64, usize) {
+    let x_start = ((-1.0 + (1.0 + 8.0 * (x_min as f64)).sqrt()) / 2.0).ceil() as i64;
+    // println!("Start at x={}", x_start);
+    let mut max_hit_y: i64 = 0;
+    let mut distinct: usize = 0;
+    for dx_init in x_start..(x_max + 1) {
+        let mut dy_init: i64 = y_min;
+        let mut previously_on_target: bool = false;
+        let mut stop_at = -1;
+        while stop_at < 0 || dy_init < stop_at {
+            let mut dx = dx_init;
+            let mut dy = dy_init;
+            let mut y: i64 = 0;
+            let mut x: i64 = 0;
+            let mut hit_target: bool = false;
+            let mut overshoot: bool = true;
+            let mut max_y: i64 = y;
+            while y >= y_min && x <= x_max {
+                if y >= y_min && y <= y_max && x >= x_min && x <= x_max {
+                    hit_target = true;
+                    break;
+                }
+                if (x < x_min && y < y_min && dy < 0) || (x >= x_min && x <= x_max && y <= y_max) {
+                    overshoot = false;
+                }
+                y += dy;
+                x += dx;
+                max_y = cmp::max(max_y, y);
+                dy -= 1;
+                if dx > 0 {
+                    dx -= 1; // Assumes only positive x
+                }
+            }
+            if (x < x_min && y < y_min && dy < 0) || (x >= x_min && x <= x_max && y <= y_max) {
+                overshoot = false;
+            }
+            if hit_target {
+                // Hit
+                previously_on_target = true;
+                distinct += 1;
+                max_hit_y = cmp::max(max_y, max_hit_y);
+            } else if stop_at < 0 && (overshoot || previously_on_target) {
+                // Note(ken.leidal): I'm not proud of this. It's super janky. But I couldn't
+                // find a reliable termination condition and this found the answer...
+                stop_at = dy_init + 1000;
+            }
+            dy_init += 1;
+        }
+    }
+    (max_hit_y, distinct)
+}
+
+fn parse_range(input: &str, value: &str, axis: &str) -> Result<(i64, i64), ParseError> {
+    // x=20..30
+    let bounds = value
+        .strip_prefix(axis)
+        .and_then(|rest| rest.strip_prefix('='))
+        .and_then(|rest| rest.split_once(".."));
+    match bounds {
+        Some((min, max)) => Ok((
+            aoc_common::parse_token(input, min, "Invalid integer")?,
+            aoc_common::parse_token(input, max, "Invalid integer")?,
+        )),
+        None => Err(ParseError::at(
+            input,
+            value,
+            format!("Expected a range like \"{}=20..30\"", axis),
+        )),
+    }
+}
+
+fn parse(input: &str) -> Result<(i64, i64, i64, i64), ParseError> {
+    // target area: x=20..30, y=-10..-5
+    let line = match aoc_common::lines(input).next() {
+        Some(line) => line,
+        None => return Err(ParseError::at_end(input, "Missing target area")),
+    };
+    let ranges = line
+        .strip_prefix("target area: ")
+        .and_then(|rest| rest.split_once(", "));
+    let (x_range, y_range) = match ranges {
+        Some(ranges) => ranges,
+        None => {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Expected \"target area: x=..., y=...\"",
+            ))
+        }
+    };
+    let (x_min, x_max) = parse_range(input, x_range, "x")?;
+    let (y_min, y_max) = parse_range(input, y_range, "y")?;
+    Ok((x_min, x_max, y_min, y_max))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = (i64, i64, i64, i64);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let &(x_min, x_max, y_min, y_max) = input;
+        Ok(find_highest_touched(x_min, x_max, y_min, y_max)
+            .0
+            .to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let &(x_min, x_max, y_min, y_max) = input;
+        Some(Ok(find_highest_touched(x_min, x_max, y_min, y_max)
+            .1
+            .to_string()))
+    }
+}
@@ -0,0 +1,244 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Forward,
+    Back,
+    ResetAim,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Forward => "forward",
+            Direction::Back => "back",
+            Direction::ResetAim => "reset-aim",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const COMMANDS: &str = "forward, back, up, down or reset-aim";
+
+fn parse_input(input: &str) -> Result<Vec<(Direction, i64)>, ParseError> {
+    let mut out: Vec<(Direction, i64)> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let direction = match parts[0] {
+            "forward" => Direction::Forward,
+            "back" => Direction::Back,
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            "reset-aim" => Direction::ResetAim,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    parts[0],
+                    format!("Unknown command, expected {}", COMMANDS),
+                ))
+            }
+        };
+        let arity = if direction == Direction::ResetAim {
+            1
+        } else {
+            2
+        };
+        if parts.len() != arity {
+            let message = match arity {
+                1 => format!("{} takes no distance", parts[0]),
+                _ => format!("Expected a distance after {}", parts[0]),
+            };
+            return Err(ParseError::at(input, line, message));
+        }
+        let distance: i64 = match arity {
+            1 => 0,
+            _ => aoc_common::parse_token(input, parts[1], "Invalid integer")?,
+        };
+        out.push((direction, distance));
+    }
+    Ok(out)
+}
+
+/// Where the submarine is after some commands.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub horiz: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+/// A rule set for how each command moves the submarine.
+pub trait MovementModel {
+    fn apply(&self, position: &mut Position, direction: Direction, distance: i64);
+
+    fn final_position(&self, directions: &[(Direction, i64)]) -> Position {
+        let mut position = Position::default();
+        for (direction, distance) in directions {
+            self.apply(&mut position, *direction, *distance);
+        }
+        position
+    }
+
+    /// The position after every command, numbered from 1.
+    fn trace(&self, directions: &[(Direction, i64)]) -> Vec<TraceStep> {
+        let mut position = Position::default();
+        let mut out: Vec<TraceStep> = Vec::new();
+        for (i, (direction, distance)) in directions.iter().enumerate() {
+            self.apply(&mut position, *direction, *distance);
+            out.push(TraceStep {
+                step: i + 1,
+                direction: *direction,
+                distance: *distance,
+                position,
+            });
+        }
+        out
+    }
+}
+
+/// One row of a trajectory trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceStep {
+    pub step: usize,
+    pub direction: Direction,
+    pub distance: i64,
+    pub position: Position,
+}
+
+/// Formats a trace as CSV with a `step,command,horiz,depth,aim` header.
+pub fn trace_csv(trace: &[TraceStep]) -> String {
+    let mut out = String::from("step,command,horiz,depth,aim\n");
+    for row in trace {
+        let command = match row.direction {
+            Direction::ResetAim => row.direction.to_string(),
+            _ => format!("{} {}", row.direction, row.distance),
+        };
+        out.push_str(&format!(
+            "{},{},{},{},{}\n",
+            row.step, command, row.position.horiz, row.position.depth, row.position.aim
+        ));
+    }
+    out
+}
+
+/// The deepest point along a trace, or 0 (the surface) for an empty one.
+pub fn max_depth(trace: &[TraceStep]) -> i64 {
+    trace
+        .iter()
+        .map(|row| row.position.depth)
+        .max()
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Part 1: up and down change depth directly; aim is never used.
+pub struct Direct;
+
+impl MovementModel for Direct {
+    fn apply(&self, position: &mut Position, direction: Direction, distance: i64) {
+        match direction {
+            Direction::Up => position.depth -= distance,
+            Direction::Down => position.depth += distance,
+            Direction::Forward => position.horiz += distance,
+            Direction::Back => position.horiz -= distance,
+            Direction::ResetAim => (),
+        }
+    }
+}
+
+/// Part 2: up and down steer the aim, and moving changes depth along it.
+pub struct Aimed;
+
+impl MovementModel for Aimed {
+    fn apply(&self, position: &mut Position, direction: Direction, distance: i64) {
+        match direction {
+            Direction::Up => position.aim -= distance,
+            Direction::Down => position.aim += distance,
+            Direction::Forward => {
+                position.horiz += distance;
+                position.depth += position.aim * distance;
+            }
+            Direction::Back => {
+                position.horiz -= distance;
+                position.depth -= position.aim * distance;
+            }
+            Direction::ResetAim => position.aim = 0,
+        }
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<(Direction, i64)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let position = Direct.final_position(input);
+        Ok((position.horiz * position.depth).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let position = Aimed.final_position(input);
+        Some(Ok((position.horiz * position.depth).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_commands() {
+        let input = "down 5\nforward 4\nback 2\nreset-aim\nforward 3\n";
+        let commands = parse_input(input).unwrap();
+        let position = Direct.final_position(&commands);
+        assert_eq!((position.horiz, position.depth), (5, 5));
+        let position = Aimed.final_position(&commands);
+        assert_eq!((position.horiz, position.depth, position.aim), (5, 10, 0));
+    }
+
+    #[test]
+    fn traces_example() {
+        let commands = parse_input(include_str!("../example.txt")).unwrap();
+        let trace = Aimed.trace(&commands);
+        assert_eq!(trace.len(), commands.len());
+        assert_eq!(
+            trace.last().unwrap().position,
+            Aimed.final_position(&commands)
+        );
+        assert_eq!(max_depth(&trace), 60);
+        assert_eq!(max_depth(&Direct.trace(&commands)), 10);
+        let csv = trace_csv(&trace[..2]);
+        assert_eq!(
+            csv,
+            "step,command,horiz,depth,aim\n1,forward 5,5,0,0\n2,down 5,5,0,5\n"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        let err = parse_input("forward 5\nsideways 3\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.text.as_str()),
+            (2, 1, "sideways")
+        );
+        assert_eq!(
+            err.message,
+            "Unknown command, expected forward, back, up, down or reset-aim"
+        );
+        let err = parse_input("reset-aim 3\n").unwrap_err();
+        assert_eq!(err.message, "reset-aim takes no distance");
+        let err = parse_input("back\n").unwrap_err();
+        assert_eq!(err.message, "Expected a distance after back");
+    }
+}
@@ -0,0 +1,129 @@
+use aoc_common::{Answer, Grid, ParseError, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: Grid<bool>,
+    /// The value of every pixel outside `pixels`.
+    default_pixel: bool,
+}
+
+impl Image {
+    fn from_array(arr: Vec<Vec<bool>>) -> Self {
+        Self {
+            pixels: Grid::from_rows(arr),
+            default_pixel: false,
+        }
+    }
+
+    fn enhance(&self, algorithm: &[bool]) -> Image {
+        let new_default = if self.default_pixel {
+            algorithm[511]
+        } else {
+            algorithm[0]
+        };
+        // Only pixels within one step of the stored area can differ from the
+        // default, so the image grows by one pixel on each side. New position
+        // (i, j) is centred on old position (i - 1, j - 1).
+        let pixels = Grid::from_fn(self.pixels.height() + 2, self.pixels.width() + 2, |pos| {
+            let mut index: usize = 0;
+            for di in -2..1 {
+                for dj in -2..1 {
+                    let value = match self.pixels.offset(pos, (di, dj)) {
+                        Some(old) => self.pixels[old],
+                        None => self.default_pixel,
+                    };
+                    index = (index << 1) | (if value { 1 } else { 0 });
+                }
+            }
+            algorithm[index]
+        });
+        Image {
+            pixels,
+            default_pixel: new_default,
+        }
+    }
+
+    fn number_lit(&self) -> Option<usize> {
+        if self.default_pixel {
+            None
+        } else {
+            Some(self.pixels.iter().filter(|x| **x).count())
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<(Vec<bool>, Image), ParseError> {
+    let to_binary = |line: &str| -> Result<Vec<bool>, ParseError> {
+        line.char_indices()
+            .map(|(i, c)| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(ParseError::at(
+                    input,
+                    &line[i..i + c.len_utf8()],
+                    "Expected '#' or '.'",
+                )),
+            })
+            .collect()
+    };
+    let sections = aoc_common::sections(input);
+    if sections.len() < 2 {
+        return Err(ParseError::at_end(input, "Missing input image"));
+    }
+    if sections.len() > 2 {
+        return Err(ParseError::at(input, sections[2], "Unexpected section"));
+    }
+    let mut alg: Vec<bool> = Vec::new();
+    for line in aoc_common::lines(sections[0]) {
+        alg.extend_from_slice(&to_binary(line)?[..]);
+    }
+    if alg.len() != 512 {
+        return Err(ParseError::at(
+            input,
+            sections[0],
+            format!("Algorithm has {} pixels, expected 512", alg.len()),
+        ));
+    }
+    let mut img_arr: Vec<Vec<bool>> = Vec::new();
+    for line in aoc_common::lines(sections[1]) {
+        let row = to_binary(line)?;
+        if !img_arr.is_empty() && row.len() != img_arr[0].len() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Row length differs from the first row",
+            ));
+        }
+        img_arr.push(row);
+    }
+    Ok((alg, Image::from_array(img_arr)))
+}
+
+fn number_lit_after(algorithm: &[bool], original_image: &Image, steps: usize) -> usize {
+    let mut image = original_image.clone();
+    for _ in 0..steps {
+        image = image.enhance(algorithm);
+    }
+    image.number_lit().expect("Uh oh, the number was infinite")
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = (Vec<bool>, Image);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (algorithm, image) = input;
+        Ok(number_lit_after(algorithm, image, 2).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let (algorithm, image) = input;
+        Some(Ok(number_lit_after(algorithm, image, 50).to_string()))
+    }
+}
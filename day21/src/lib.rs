@@ -0,0 +1,203 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+fn part1(player1_start: u64, player2_start: u64) -> u64 {
+    let mut positions = [player1_start, player2_start];
+    let mut scores: Vec<u64> = vec![0, 0];
+    let mut die: u64 = 1;
+    let mut turn: usize = 0;
+    let mut rolls: u64 = 0;
+    while !scores.iter().any(|score| *score >= 1000) {
+        let move_positions: u64 = (0..3)
+            .map(|_| {
+                let value = die;
+                die = (die % 100) + 1;
+                rolls += 1;
+                value
+            })
+            .sum();
+        let my_pos = positions[turn];
+        let new_pos = ((my_pos + move_positions - 1) % 10) + 1;
+        positions[turn] = new_pos;
+        scores[turn] += new_pos;
+        turn = (turn + 1) % positions.len();
+    }
+    scores.iter().min().unwrap() * rolls
+}
+
+#[derive(Debug, Clone)]
+struct GameState {
+    turn: usize,
+    scores: Vec<u64>,
+    positions: Vec<u64>,
+}
+
+impl PartialEq for GameState {
+    fn eq(&self, other: &Self) -> bool {
+        self.turn == other.turn
+            && self.scores.len() == other.scores.len()
+            && self
+                .scores
+                .iter()
+                .zip(other.scores.iter())
+                .all(|(x, y)| x == y)
+            && self.positions.len() == other.positions.len()
+            && self
+                .positions
+                .iter()
+                .zip(other.positions.iter())
+                .all(|(x, y)| x == y)
+    }
+}
+
+impl Eq for GameState {}
+
+impl Hash for GameState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.turn.hash(state);
+        self.scores.len().hash(state);
+        self.positions.len().hash(state);
+        for score in self.scores.iter() {
+            score.hash(state);
+        }
+        for position in self.positions.iter() {
+            position.hash(state);
+        }
+    }
+}
+
+impl GameState {
+    fn initial(player1_start: u64, player2_start: u64) -> Self {
+        Self {
+            turn: 0,
+            scores: vec![0, 0],
+            positions: vec![player1_start, player2_start],
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.scores.iter().any(|x| *x >= 21)
+    }
+
+    fn round(&self) -> Vec<Self> {
+        if self.finished() {
+            return vec![self.clone()];
+        }
+        let turn = self.turn;
+        let mut out: Vec<Self> = Vec::new();
+        for roll1 in 1..4 {
+            for roll2 in 1..4 {
+                for roll3 in 1..4 {
+                    let mut positions = self.positions.clone();
+                    let mut scores = self.scores.clone();
+
+                    let move_positions = roll1 + roll2 + roll3;
+                    let my_pos = positions[turn];
+                    let new_pos = ((my_pos + move_positions - 1) % 10) + 1;
+                    positions[turn] = new_pos;
+                    scores[turn] += new_pos;
+
+                    let new_turn = (turn + 1) % positions.len();
+
+                    out.push(Self {
+                        turn: new_turn,
+                        scores,
+                        positions,
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Universes in which the luckier player wins with the Dirac die.
+///
+/// Before every round, `on_round` gets the number of games still underway
+/// and the number of games in total.
+pub fn part2(
+    player1_start: u64,
+    player2_start: u64,
+    mut on_round: impl FnMut(usize, usize),
+) -> usize {
+    let mut states: HashMap<GameState, usize> = HashMap::new();
+    states.insert(GameState::initial(player1_start, player2_start), 1);
+    while !states.iter().all(|(k, _)| k.finished()) {
+        on_round(
+            states
+                .iter()
+                .map(|(k, v)| if k.finished() { 0 } else { *v })
+                .sum::<usize>(),
+            states.values().copied().sum::<usize>(),
+        );
+        let mut new_states: HashMap<GameState, usize> = HashMap::new();
+        for (state, count) in states.iter() {
+            for new_state in state.round() {
+                *new_states.entry(new_state).or_insert(0) += count;
+            }
+        }
+        states = new_states
+    }
+    (0..2)
+        .map(|player| {
+            states
+                .iter()
+                .map(|(state, games)| {
+                    if state.scores[player] > state.scores[1 - player] {
+                        *games
+                    } else {
+                        0
+                    }
+                })
+                .sum::<usize>()
+        })
+        .max()
+        .unwrap()
+}
+
+fn parse(input: &str) -> Result<(u64, u64), ParseError> {
+    // Player 1 starting position: 4
+    let mut positions: Vec<u64> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let position = match line.rsplit_once(": ") {
+            Some((_, position)) => position,
+            None => return Err(ParseError::at(input, line, "Missing starting position")),
+        };
+        let value: u64 = aoc_common::parse_token(input, position, "Invalid starting position")?;
+        if !(1..=10).contains(&value) {
+            return Err(ParseError::at(
+                input,
+                position,
+                "Starting position must be between 1 and 10",
+            ));
+        }
+        if positions.len() == 2 {
+            return Err(ParseError::at(input, line, "Expected exactly two players"));
+        }
+        positions.push(value);
+    }
+    if positions.len() != 2 {
+        return Err(ParseError::at_end(input, "Expected exactly two players"));
+    }
+    Ok((positions[0], positions[1]))
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = (u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(part1(input.0, input.1).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(part2(input.0, input.1, |_, _| {}).to_string()))
+    }
+}
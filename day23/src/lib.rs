@@ -0,0 +1,533 @@
+use aoc_common::{search, Answer, ParseError, Solution, SolveError};
+use std::collections::{HashMap, HashSet, LinkedList};
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Copy)]
+enum NodeKind {
+    Hallway,
+    HallwayDoor,
+    Room,
+}
+
+#[derive(Debug)]
+struct Node {
+    y: usize,
+    x: usize,
+    kind: NodeKind,
+}
+
+#[derive(Debug)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    adjacency: HashMap<usize, HashSet<usize>>,
+}
+
+impl Graph {
+    /// Steps from `node_id` to the nearest cell of the amphipod's room, if it can get there.
+    fn amph_dist_to_home(&self, node_id: usize, amph_kind: usize) -> Option<usize> {
+        let mut room_xs: HashSet<usize> = HashSet::new();
+        for node in self.nodes.iter() {
+            if let NodeKind::Room = node.kind {
+                room_xs.insert(node.x);
+            }
+        }
+        let mut room_xs: Vec<usize> = room_xs.into_iter().collect();
+        room_xs.sort();
+        let dest_x = *room_xs.get(amph_kind)?;
+
+        // BFS
+        let mut queue: LinkedList<(usize, usize)> = LinkedList::new();
+        let mut visited: HashSet<usize> = HashSet::new();
+        queue.push_back((node_id, 0));
+        visited.insert(node_id);
+        while !queue.is_empty() {
+            let (node, dist) = queue.pop_front().unwrap();
+            let node_x = self.nodes[node].x;
+            if node_x == dest_x {
+                if let NodeKind::Room = self.nodes[node].kind {
+                    return Some(dist);
+                }
+            }
+            for adj in self.adjacency[&node].iter() {
+                if !visited.contains(adj) {
+                    queue.push_back((*adj, dist + 1));
+                    visited.insert(*adj);
+                }
+            }
+        }
+        None
+    }
+
+    fn amph_is_home(&self, node_id: usize, amph_kind: usize) -> bool {
+        let node = &self.nodes[node_id];
+        match node.kind {
+            NodeKind::Room => (),
+            _ => {
+                return false;
+            }
+        }
+
+        let mut room_xs: HashSet<usize> = HashSet::new();
+        for node in self.nodes.iter() {
+            if let NodeKind::Room = node.kind {
+                room_xs.insert(node.x);
+            }
+        }
+        let mut room_xs: Vec<usize> = room_xs.into_iter().collect();
+        room_xs.sort();
+        room_xs.get(amph_kind) == Some(&node.x)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AmphipodState {
+    Unmoved,
+    MovedOut,
+    MovedIn,
+}
+
+impl PartialEq for AmphipodState {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            AmphipodState::Unmoved => matches!(other, AmphipodState::Unmoved),
+            AmphipodState::MovedOut => matches!(other, AmphipodState::MovedOut),
+            AmphipodState::MovedIn => matches!(other, AmphipodState::MovedIn),
+        }
+    }
+}
+
+impl Eq for AmphipodState {}
+
+impl Hash for AmphipodState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            AmphipodState::Unmoved => 0_u8.hash(state),
+            AmphipodState::MovedOut => 1_u8.hash(state),
+            AmphipodState::MovedIn => 2_u8.hash(state),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    node_to_amphipod: HashMap<usize, (usize, AmphipodState)>,
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        if self.node_to_amphipod.len() != other.node_to_amphipod.len() {
+            return false;
+        }
+        for (key, val1) in self.node_to_amphipod.iter() {
+            if !other.node_to_amphipod.contains_key(key) {
+                return false;
+            }
+            if other.node_to_amphipod[key] != *val1 {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Eq for State {}
+
+impl Hash for State {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_to_amphipod.len().hash(state);
+        for (key, val) in self.node_to_amphipod.iter() {
+            key.hash(state);
+            val.hash(state);
+        }
+    }
+}
+
+impl State {
+    fn finished(&self, graph: &Graph) -> bool {
+        let mut x_coords: Vec<usize> = vec![0, 0, 0, 0];
+        for (node_id, node) in graph.nodes.iter().enumerate() {
+            match node.kind {
+                NodeKind::Hallway => (),
+                NodeKind::HallwayDoor => (),
+                NodeKind::Room => {
+                    for back_node_id in find_room_stack(graph, node_id) {
+                        if self.node_to_amphipod.contains_key(&back_node_id)
+                            && self.node_to_amphipod.contains_key(&node_id)
+                        {
+                            let amph_kind = self.node_to_amphipod[&back_node_id].0;
+                            if amph_kind == self.node_to_amphipod[&node_id].0 {
+                                x_coords[amph_kind] = node.x;
+                                // Good
+                            } else {
+                                return false;
+                            }
+                        } else {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+        x_coords[3] > x_coords[2] && x_coords[2] > x_coords[1] && x_coords[1] > x_coords[0]
+    }
+}
+
+fn parse(input: &str) -> Result<(Graph, State), ParseError> {
+    let mut grid: Vec<Vec<(Option<NodeKind>, Option<usize>)>> = Vec::new();
+    for line in aoc_common::raw_lines(input) {
+        let row: Vec<(Option<NodeKind>, Option<usize>)> = line
+            .char_indices()
+            .map(|(i, c)| match c {
+                '#' => Ok((None, None)),
+                ' ' => Ok((None, None)),
+                '.' => Ok((Some(NodeKind::Hallway), None)),
+                'A' => Ok((Some(NodeKind::Room), Some(0))),
+                'B' => Ok((Some(NodeKind::Room), Some(1))),
+                'C' => Ok((Some(NodeKind::Room), Some(2))),
+                'D' => Ok((Some(NodeKind::Room), Some(3))),
+                _ => Err(ParseError::at(
+                    input,
+                    &line[i..i + c.len_utf8()],
+                    "Unknown character",
+                )),
+            })
+            .collect::<Result<Vec<(Option<NodeKind>, Option<usize>)>, ParseError>>()?;
+        grid.push(row);
+    }
+
+    let height = grid.len();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut loc_to_node: HashMap<(isize, isize), usize> = HashMap::new();
+    for y in 0..height {
+        let width = grid[y].len();
+        for x in 0..width {
+            let (kind_opt, _) = &grid[y][x];
+            match kind_opt {
+                None => (),
+                Some(kind) => {
+                    let my_kind = match kind {
+                        NodeKind::Hallway => {
+                            let below = if y + 1 < height && grid[y + 1].len() > x {
+                                grid[y + 1][x].0
+                            } else {
+                                None
+                            };
+
+                            match below {
+                                Some(_) => NodeKind::HallwayDoor,
+                                None => NodeKind::Hallway,
+                            }
+                        }
+                        NodeKind::Room => NodeKind::Room,
+                        _ => panic!("Unexpected value"),
+                    };
+                    let node_id = nodes.len();
+                    nodes.push(Node {
+                        y,
+                        x,
+                        kind: my_kind,
+                    });
+                    loc_to_node.insert((y.try_into().unwrap(), x.try_into().unwrap()), node_id);
+                }
+            }
+        }
+    }
+
+    let mut adjacency: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for ((y, x), node_id) in loc_to_node.iter() {
+        adjacency.insert(*node_id, HashSet::new());
+        for (dy, dx) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
+            let y2 = dy + y;
+            let x2 = dx + x;
+            let key = (y2, x2);
+            if loc_to_node.contains_key(&key) {
+                let neighbor = loc_to_node[&key];
+                adjacency.entry(*node_id).or_default().insert(neighbor);
+            }
+        }
+    }
+
+    let mut node_to_amphipod: HashMap<usize, (usize, AmphipodState)> = HashMap::new();
+    for ((y, x), node_id) in loc_to_node.iter() {
+        let yu: usize = (*y).try_into().unwrap();
+        let xu: usize = (*x).try_into().unwrap();
+        if let Some(amphipod_type) = grid[yu][xu].1 {
+            node_to_amphipod.insert(*node_id, (amphipod_type, AmphipodState::Unmoved));
+        }
+    }
+
+    let mut per_kind: Vec<usize> = vec![0; 4];
+    for (kind, _) in node_to_amphipod.values() {
+        per_kind[*kind] += 1;
+    }
+    if per_kind.iter().any(|n| *n != per_kind[0]) || per_kind[0] == 0 {
+        return Err(ParseError::at_end(
+            input,
+            "Each amphipod kind must appear equally often",
+        ));
+    }
+
+    let graph = Graph { nodes, adjacency };
+    let state = State { node_to_amphipod };
+
+    Ok((graph, state))
+}
+
+fn find_accessible_nodes(
+    graph: &Graph,
+    state: &State,
+    node_id: usize,
+    amphipod_type: usize,
+) -> Vec<(usize, usize)> {
+    let mut out: Vec<(usize, usize)> = Vec::new();
+    let mut stack: LinkedList<(usize, usize)> = LinkedList::new();
+    let mut will_visit: HashSet<usize> = HashSet::new();
+    stack.push_back((node_id, 0));
+    will_visit.insert(node_id);
+    while !stack.is_empty() {
+        let (current_node, cost) = stack.pop_back().unwrap();
+        if current_node != node_id {
+            out.push((current_node, cost));
+        }
+        for neighbor in graph.adjacency[&current_node].iter() {
+            if will_visit.contains(neighbor) {
+                continue;
+            }
+            if state.node_to_amphipod.contains_key(neighbor) {
+                continue;
+            }
+            will_visit.insert(*neighbor);
+            stack.push_back((*neighbor, cost + 10_usize.pow(amphipod_type as u32)));
+        }
+    }
+    out
+}
+
+fn find_room_stack(graph: &Graph, node_id: usize) -> Vec<usize> {
+    let mut out: Vec<usize> = Vec::new();
+    let my_node = &graph.nodes[node_id];
+    let my_x = my_node.x;
+    for (i, node) in graph.nodes.iter().enumerate() {
+        if node.x == my_x {
+            if let NodeKind::Room = node.kind {
+                out.push(i);
+            }
+        }
+    }
+    out
+}
+
+fn find_room_stack_below(graph: &Graph, node_id: usize) -> Vec<usize> {
+    let mut out: Vec<usize> = Vec::new();
+    let my_node = &graph.nodes[node_id];
+    let my_x = my_node.x;
+    let my_y = my_node.y;
+    for (i, node) in graph.nodes.iter().enumerate() {
+        if node.x == my_x && node.y > my_y {
+            if let NodeKind::Room = node.kind {
+                out.push(i);
+            }
+        }
+    }
+    out
+}
+
+// fn find_adjacent_back(graph: &Graph, node_id: usize) -> Option<usize> {
+//     for adj in graph.adjacency[&node_id].iter() {
+//         let node = &graph.nodes[*adj];
+//         if let NodeKind::RoomBack = node.kind {
+//             return Some(*adj);
+//         }
+//     }
+//     None
+// }
+
+fn possible_next_states(graph: &Graph, state: &State) -> Vec<(State, usize)> {
+    let mut costs: Vec<(State, usize)> = Vec::new();
+    for (node_id, (amphipod_type, amphi_state)) in state.node_to_amphipod.iter() {
+        match amphi_state {
+            AmphipodState::Unmoved => {
+                for (dest_id, cost) in find_accessible_nodes(graph, state, *node_id, *amphipod_type)
+                {
+                    let dest = &graph.nodes[dest_id];
+                    match dest.kind {
+                        NodeKind::Hallway => {
+                            let mut new_node_to_amphipod = state.node_to_amphipod.clone();
+                            new_node_to_amphipod.remove(node_id);
+                            new_node_to_amphipod
+                                .insert(dest_id, (*amphipod_type, AmphipodState::MovedOut));
+                            costs.push((
+                                State {
+                                    node_to_amphipod: new_node_to_amphipod,
+                                },
+                                cost,
+                            ));
+                        }
+                        NodeKind::HallwayDoor => (),
+                        NodeKind::Room => (),
+                    }
+                }
+            }
+            AmphipodState::MovedOut => {
+                for (dest_id, cost) in find_accessible_nodes(graph, state, *node_id, *amphipod_type)
+                {
+                    let dest = &graph.nodes[dest_id];
+                    match dest.kind {
+                        NodeKind::Hallway => (),
+                        NodeKind::HallwayDoor => (),
+                        NodeKind::Room => {
+                            if graph.amph_is_home(dest_id, *amphipod_type) {
+                                let mut valid: bool = true;
+                                for back_id in find_room_stack_below(graph, dest_id) {
+                                    if !state.node_to_amphipod.contains_key(&back_id) {
+                                        // Invalid, can't have blank spaces below
+                                        valid = false;
+                                        break;
+                                    }
+                                    let neighbor_amphi_type = state.node_to_amphipod[&back_id].0;
+                                    if neighbor_amphi_type != *amphipod_type {
+                                        // Invalid, can't mix amphipod types
+                                        valid = false;
+                                        break;
+                                    }
+                                }
+                                if valid {
+                                    // Valid placement
+                                    let mut new_node_to_amphipod = state.node_to_amphipod.clone();
+                                    new_node_to_amphipod.remove(node_id);
+                                    new_node_to_amphipod
+                                        .insert(dest_id, (*amphipod_type, AmphipodState::MovedIn));
+                                    costs.push((
+                                        State {
+                                            node_to_amphipod: new_node_to_amphipod,
+                                        },
+                                        cost,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            AmphipodState::MovedIn => (),
+        }
+    }
+    costs
+}
+
+/// A lower bound on the energy left, or `None` if some amphipod can't reach its room.
+fn heuristic(graph: &Graph, initial_state: &State) -> Option<usize> {
+    // Distance between like amphipods
+    // let mut amphipod_to_node: HashMap<usize, HashSet<usize>> = HashMap::new();
+    // for (node_id, (amphi_type, _)) in initial_state.node_to_amphipod.iter() {
+    //     amphipod_to_node.entry(*amphi_type).or_insert(HashSet::new()).insert(*node_id);
+    // }
+    // let mut cost: usize = 0;
+    // for (amphi_type, nodes) in amphipod_to_node.iter() {
+    //     assert_eq!(nodes.len(), 2);
+    //     let v: Vec<usize> = nodes.into_iter().cloned().collect();
+    //     let p1 = v[0];
+    //     let p2 = v[1];
+    //     cost += 10_usize.pow(*amphi_type as u32) * (graph.distance_between(p1, p2) - 1);
+    // }
+    // cost
+
+    // Distance between amphipod and destination
+    let mut cost: usize = 0;
+    for (node_id, (amphi_type, _)) in initial_state.node_to_amphipod.iter() {
+        cost +=
+            10_usize.pow(*amphi_type as u32) * graph.amph_dist_to_home(*node_id, *amphi_type)?;
+    }
+    Some(cost)
+}
+
+fn shortest_path(graph: &Graph, initial_state: &State) -> Option<usize> {
+    // Amphipods only move along the graph, so once every one of them can
+    // reach its room they always can.
+    heuristic(graph, initial_state)?;
+    let path = search::shortest_path(
+        initial_state.clone(),
+        |state| possible_next_states(graph, state),
+        |state| state.finished(graph),
+        Some(&mut |state: &State| {
+            heuristic(graph, state).expect("Amphipods stay connected to their rooms")
+        }),
+    );
+    path.map(|path| path.cost)
+}
+
+/// Unfolds the diagram for part 2 by inserting the two extra rows into each
+/// room, or `None` if it is too short to have rooms below the hallway.
+fn unfold(input: &str) -> Option<String> {
+    let mut lines: Vec<&str> = aoc_common::raw_lines(input).collect();
+    if lines.len() < 3 {
+        return None;
+    }
+    lines.splice(3..3, ["  #D#C#B#A#", "  #D#B#A#C#"]);
+    Some(lines.join("\n"))
+}
+
+/// A parsed burrow: the map and where the amphipods start.
+type Burrow = (Graph, State);
+
+/// The diagram as given, and unfolded for part 2 when that is possible.
+pub struct Diagram {
+    folded: Burrow,
+    unfolded: Option<Burrow>,
+}
+
+/// The least energy needed to organize `burrow`.
+fn least_energy(burrow: &Burrow) -> Answer {
+    let (graph, state) = burrow;
+    match shortest_path(graph, state) {
+        Some(cost) => Ok(cost.to_string()),
+        None => Err(SolveError::new("the amphipods can't be organized")),
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Input = Diagram;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Diagram {
+            folded: parse(input)?,
+            unfolded: unfold(input).and_then(|unfolded| parse(&unfolded).ok()),
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        least_energy(&input.folded)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(match &input.unfolded {
+            Some(burrow) => least_energy(burrow),
+            None => Err(SolveError::new("the diagram can't be unfolded")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_unreachable_rooms() {
+        // Every room cell is in one column, so only A has a room to go to.
+        let diagram = Day23::parse("#.#\n#B#\n#A#\n#C#\n#D#\n").unwrap();
+        assert_eq!(
+            Day23::part1(&diagram).unwrap_err().to_string(),
+            "the amphipods can't be organized"
+        );
+        assert!(Day23::part2(&diagram).unwrap().is_err());
+
+        let diagram = Day23::parse("#B#A#C#D#").unwrap();
+        assert_eq!(
+            Day23::part2(&diagram).unwrap().unwrap_err().to_string(),
+            "the diagram can't be unfolded"
+        );
+    }
+}
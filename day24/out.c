@@ -23,8 +23,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 10;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -41,8 +41,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 12;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -59,8 +59,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 10;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -77,8 +77,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 12;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -95,8 +95,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 11;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -113,8 +113,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -16;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -131,8 +131,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 10;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -149,8 +149,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -11;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -167,8 +167,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -13;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -185,8 +185,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 1;
   x += 13;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -203,8 +203,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -8;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -221,8 +221,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -1;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -239,8 +239,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -4;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
@@ -257,8 +257,8 @@ int run_program(const int64_t* inputs) {
   x %= 26;
   z /= 26;
   x += -14;
-  x = (x == w) ? 1 : 0;
-  x = (x == 0) ? 1 : 0;
+  x = (x == w);
+  x = (x == 0);
   y *= 0;
   y += 25;
   y *= x;
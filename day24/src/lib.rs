@@ -0,0 +1,1206 @@
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+use std::cmp;
+use std::collections::{HashMap, LinkedList, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Constant(i64),
+    Variable(char),
+}
+
+impl Operand {
+    fn get_var(&self) -> char {
+        match self {
+            Operand::Constant(_) => panic!("Expected variable"),
+            Operand::Variable(c) => *c,
+        }
+    }
+
+    fn get_value(&self, state: &State) -> i64 {
+        match self {
+            Operand::Constant(c) => *c,
+            Operand::Variable(c) => state.variable_store[c],
+        }
+    }
+
+    fn parse(input: &str, value: &str) -> Result<Operand, ParseError> {
+        match value.trim().parse::<i64>() {
+            Err(_) => match value {
+                "w" | "x" | "y" | "z" => Ok(Operand::Variable(value.chars().nth(0).unwrap())),
+                _ => Err(ParseError::at(input, value, "Expected a variable or an integer")),
+            },
+            Ok(x) => Ok(Operand::Constant(x)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ASTNode {
+    Constant(i64),
+    Inp(u8),
+    Add(Rc<ASTNode>, Rc<ASTNode>),
+    Mul(Rc<ASTNode>, Rc<ASTNode>),
+    Div(Rc<ASTNode>, Rc<ASTNode>),
+    Mod(Rc<ASTNode>, Rc<ASTNode>),
+    Eql(Rc<ASTNode>, Rc<ASTNode>),
+}
+
+
+impl PartialEq for ASTNode {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            ASTNode::Constant(x) => match other {
+                ASTNode::Constant(y) => x == y,
+                _ => false,
+            },
+            ASTNode::Inp(x) => match other {
+                ASTNode::Inp(y) => x == y,
+                _ => false,
+            },
+            ASTNode::Add(x1, y1) => match other {
+                ASTNode::Add(x2, y2) => x1 == x2 && y1 == y2,
+                _ => false,
+            },
+            ASTNode::Mul(x1, y1) => match other {
+                ASTNode::Mul(x2, y2) => x1 == x2 && y1 == y2,
+                _ => false,
+            },
+            ASTNode::Div(x1, y1) => match other {
+                ASTNode::Div(x2, y2) => x1 == x2 && y1 == y2,
+                _ => false,
+            },
+            ASTNode::Mod(x1, y1) => match other {
+                ASTNode::Mod(x2, y2) => x1 == x2 && y1 == y2,
+                _ => false,
+            },
+            ASTNode::Eql(x1, y1) => match other {
+                ASTNode::Eql(x2, y2) => x1 == x2 && y1 == y2,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for ASTNode {}
+
+impl Hash for ASTNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ASTNode::Constant(x) => {
+                0u8.hash(state);
+                x.hash(state);
+            },
+            ASTNode::Inp(x) => {
+                1u8.hash(state);
+                x.hash(state);
+            },
+            ASTNode::Add(x1, y1) => {
+                2u8.hash(state);
+                x1.hash(state);
+                y1.hash(state);
+            },
+            ASTNode::Mul(x1, y1) => {
+                3u8.hash(state);
+                x1.hash(state);
+                y1.hash(state);
+            },
+            ASTNode::Div(x1, y1) => {
+                4u8.hash(state);
+                x1.hash(state);
+                y1.hash(state);
+            },
+            ASTNode::Mod(x1, y1) => {
+                5u8.hash(state);
+                x1.hash(state);
+                y1.hash(state);
+            },
+            ASTNode::Eql(x1, y1) => {
+                6u8.hash(state);
+                x1.hash(state);
+                y1.hash(state);
+            },
+        }
+    }
+}
+
+impl ASTNode {
+    fn render(self: &Rc<Self>, indent: usize, depth_limit: usize, out: &mut String) {
+        if depth_limit == 0 {
+            return
+        }
+        let prefix = (0..(2 * indent)).map(|_| " ").collect::<String>();
+        match &**self {
+            ASTNode::Constant(x) => {
+                out.push_str(&format!("{}- {}\n", prefix, x));
+            },
+            ASTNode::Inp(x) => {
+                out.push_str(&format!("{}- input[{}]\n", prefix, x));
+            },
+            ASTNode::Add(x, y) => {
+                out.push_str(&format!("{}- add:\n", prefix));
+                ASTNode::render(x, indent + 1, depth_limit - 1, out);
+                ASTNode::render(y, indent + 1, depth_limit - 1, out);
+            },
+            ASTNode::Mul(x, y) => {
+                out.push_str(&format!("{}- mul:\n", prefix));
+                ASTNode::render(x, indent + 1, depth_limit - 1, out);
+                ASTNode::render(y, indent + 1, depth_limit - 1, out);
+            },
+            ASTNode::Div(x, y) => {
+                out.push_str(&format!("{}- div:\n", prefix));
+                ASTNode::render(x, indent + 1, depth_limit - 1, out);
+                ASTNode::render(y, indent + 1, depth_limit - 1, out);
+            },
+            ASTNode::Mod(x, y) => {
+                out.push_str(&format!("{}- mod:\n", prefix));
+                ASTNode::render(x, indent + 1, depth_limit - 1, out);
+                ASTNode::render(y, indent + 1, depth_limit - 1, out);
+            },
+            ASTNode::Eql(x, y) => {
+                out.push_str(&format!("{}- eql:\n", prefix));
+                ASTNode::render(x, indent + 1, depth_limit - 1, out);
+                ASTNode::render(y, indent + 1, depth_limit - 1, out);
+            },
+        }
+    }
+
+    pub fn eval(self: &Rc<Self>, inputs: &[i64]) -> i64 {
+        match &**self {
+            ASTNode::Constant(x) => *x,
+            ASTNode::Inp(x) => inputs[*x as usize],
+            ASTNode::Add(x, y) => ASTNode::eval(x, inputs) + ASTNode::eval(y, inputs),
+            ASTNode::Mul(x, y) => ASTNode::eval(x, inputs) * ASTNode::eval(y, inputs),
+            ASTNode::Div(x, y) => ASTNode::eval(x, inputs) / ASTNode::eval(y, inputs),
+            ASTNode::Mod(x, y) => ASTNode::eval(x, inputs) % ASTNode::eval(y, inputs),
+            ASTNode::Eql(x, y) => if ASTNode::eval(x, inputs) == ASTNode::eval(y, inputs) { 1 } else { 0 },
+        }
+    }
+
+    fn rops(self: &Rc<Self>, cache: &mut HashSet<Rc<ASTNode>>) -> usize {
+        if cache.contains(self) {
+            return 0;
+        }
+        cache.insert(Rc::clone(self));
+
+        match &**self {
+            ASTNode::Constant(_) => 0,
+            ASTNode::Inp(_) => 0,
+            ASTNode::Add(x, y) => ASTNode::rops(x, cache) + ASTNode::rops(y, cache) + 1,
+            ASTNode::Mul(x, y) => ASTNode::rops(x, cache) + ASTNode::rops(y, cache) + 1,
+            ASTNode::Div(x, y) => ASTNode::rops(x, cache) + ASTNode::rops(y, cache) + 1,
+            ASTNode::Mod(x, y) => ASTNode::rops(x, cache) + ASTNode::rops(y, cache) + 1,
+            ASTNode::Eql(x, y) => ASTNode::rops(x, cache) + ASTNode::rops(y, cache) + 1,
+
+        }
+    }
+    fn ops(self: &Rc<Self>) -> usize {
+        let mut cache: HashSet<Rc<ASTNode>> = HashSet::new();
+        ASTNode::rops(self, &mut cache)
+    }
+
+    fn r_inputs_used(self: &Rc<Self>, used: &mut HashSet<u8>) {
+        match &**self {
+            ASTNode::Constant(_) => (),
+            ASTNode::Inp(x) => {
+                used.insert(*x);
+            },
+            ASTNode::Add(x, y) => {
+                ASTNode::r_inputs_used(x, used);
+                ASTNode::r_inputs_used(y, used);
+            },
+            ASTNode::Mul(x, y) => {
+                ASTNode::r_inputs_used(x, used);
+                ASTNode::r_inputs_used(y, used);
+            },
+            ASTNode::Div(x, y) => {
+                ASTNode::r_inputs_used(x, used);
+                ASTNode::r_inputs_used(y, used);
+            },
+            ASTNode::Mod(x, y) => {
+                ASTNode::r_inputs_used(x, used);
+                ASTNode::r_inputs_used(y, used);
+            },
+            ASTNode::Eql(x, y) => {
+                ASTNode::r_inputs_used(x, used);
+                ASTNode::r_inputs_used(y, used);
+            },
+        }
+    }
+
+    fn inputs_used(self: &Rc<Self>) -> HashSet<u8> {
+        let mut used: HashSet<u8> = HashSet::new();
+        ASTNode::r_inputs_used(self, &mut used);
+        used
+    }
+
+    #[allow(dead_code)]
+    fn rreplace_common(self: &Rc<Self>, cache: &mut HashSet<Rc<ASTNode>>) -> Rc<ASTNode> {
+        if cache.contains(self) {
+            return Rc::clone(cache.get(self).unwrap());
+        }
+
+        let common = match &**self {
+            ASTNode::Constant(_) => Rc::clone(self),
+            ASTNode::Inp(_) => Rc::clone(self),
+            ASTNode::Add(x, y) => Rc::new(ASTNode::Add(ASTNode::rreplace_common(x, cache), ASTNode::rreplace_common(y, cache))),
+            ASTNode::Mul(x, y) => Rc::new(ASTNode::Mul(ASTNode::rreplace_common(x, cache), ASTNode::rreplace_common(y, cache))),
+            ASTNode::Div(x, y) => Rc::new(ASTNode::Div(ASTNode::rreplace_common(x, cache), ASTNode::rreplace_common(y, cache))),
+            ASTNode::Mod(x, y) => Rc::new(ASTNode::Mod(ASTNode::rreplace_common(x, cache), ASTNode::rreplace_common(y, cache))),
+            ASTNode::Eql(x, y) => Rc::new(ASTNode::Eql(ASTNode::rreplace_common(x, cache), ASTNode::rreplace_common(y, cache))),
+        };
+
+        if cache.contains(&common) {
+            return Rc::clone(cache.get(&common).unwrap());
+        }
+        cache.insert(Rc::clone(&common));
+        common
+    }
+
+    #[allow(dead_code)]
+    fn replace_common(self: &Rc<Self>) -> Rc<Self> {
+        let mut cache: HashSet<Rc<ASTNode>> = HashSet::new();
+        ASTNode::rreplace_common(self, &mut cache)
+    }
+
+    // fn reval(self: &Rc<Self>, inputs: Vec<i64>, cache: HashMap<*const Self, i64>) -> i64 {
+    //     let ptr = self.as_ptr();
+    //     if cache.contains_key(&ptr) {
+    //         return cache[&ptr]
+    //     }
+    //     // TODO: use the ptr as a cache key for the value of the sub tree. Recurse and evaluate.
+
+
+    // }
+
+    // fn eval(self: &Rc<Self>, inputs: Vec<i64>) -> i64 {
+    //     return reval(self, inputs, HashMap::new());
+    // }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Inp(Operand),
+    Add(Operand, Operand),
+    Mul(Operand, Operand),
+    Div(Operand, Operand),
+    Mod(Operand, Operand),
+    Eql(Operand, Operand),
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    input_queue: LinkedList<i64>,
+    variable_store: HashMap<char, i64>,
+    pc: usize,
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        if other.input_queue.len() != self.input_queue.len() {
+            return false;
+        }
+        if other.variable_store.len() != self.variable_store.len() {
+            return false;
+        }
+        if other.pc != self.pc {
+            return false;
+        }
+        for (v1, v2) in self.input_queue.iter().zip(other.input_queue.iter()) {
+            if v1 != v2 {
+                return false;
+            }
+        }
+        for (key, val) in self.variable_store.iter() {
+            if !other.variable_store.contains_key(key) {
+                return false;
+            }
+            if other.variable_store[key] != *val {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Eq for State {}
+
+impl Hash for State {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.input_queue.len().hash(state);
+        self.variable_store.len().hash(state);
+        self.pc.hash(state);
+        for v1 in self.input_queue.iter() {
+            v1.hash(state);
+        }
+        let mut keys = self.variable_store.keys().cloned().collect::<Vec<char>>();
+        keys.sort();
+        for key in keys {
+            key.hash(state);
+            self.variable_store[&key].hash(state);
+        }
+    }
+}
+
+pub struct SmartComputer {
+    program: Vec<Op>,
+    #[allow(dead_code)]
+    cache: HashMap<State, bool>,
+}
+
+#[derive(Debug)]
+struct Range {
+    low: Option<i64>,
+    high: Option<i64>,
+}
+
+impl Range {
+    fn constant(&self) -> Option<i64> {
+        let (low, high) = self.full_range()?;
+        if low == high {
+            Some(low)
+        } else {
+            None
+        }
+    }
+
+    fn full_range(&self) -> Option<(i64,i64)> {
+        let low = self.low?;
+        let high = self.high?;
+        Some((low, high))
+    }
+
+    fn can_intersect(&self, other: &Self) -> bool {
+        !(match self.low {
+            None => match other.low {
+                None => true,
+                Some(their_low) => match self.high {
+                    None => true,
+                    Some(high_val) => their_low > high_val,
+                },
+            },
+            Some(low_val) => match other.high {
+                None => match self.high {
+                    None => true,
+                    Some(high_val) => match other.low {
+                        None => true,
+                        Some(their_low) => their_low > high_val,
+                    }
+                },
+                Some(their_high) => their_high < low_val,
+            },
+        })
+    }
+}
+
+impl SmartComputer {
+    #[allow(dead_code)]
+    fn run(&mut self, input: usize, digits: usize) -> bool {
+        let mut current = input;
+        let mut inputs: Vec<i64> = vec![0; digits];
+        for i in 0..digits {
+            inputs[digits - 1 - i] = (current % 10) as i64;
+            current /= 10;
+        }
+
+        let mut var_store: HashMap<char, i64> = HashMap::new();
+        var_store.insert('w', 0);
+        var_store.insert('x', 0);
+        var_store.insert('y', 0);
+        var_store.insert('z', 0);
+        let mut states: Vec<State> = vec![State {
+            input_queue: LinkedList::from_iter(inputs.iter().cloned()),
+            variable_store: var_store,
+            pc: 0,
+        }];
+        // println!("Input queue: {:?}", states[states.len() - 1].input_queue);
+        // println!("Program: {:?}", self.program);
+        while states[states.len() - 1].pc < self.program.len() && !self.cache.contains_key(&states[states.len() - 1]) {
+            let mut state = states[states.len() - 1].clone();
+            // println!("Op {}: {:?}", state.pc, self.program[state.pc]);
+            match self.program[state.pc] {
+                Op::Inp(var) => state.variable_store.insert(var.get_var(), match state.input_queue.pop_front() {
+                    None => panic!("Requested input when exhausted!"),
+                    Some(inp) => inp,
+                }),
+                Op::Add(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) + rhs.get_value(&state)),
+                Op::Mul(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) * rhs.get_value(&state)),
+                Op::Div(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) / rhs.get_value(&state)),
+                Op::Mod(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) % rhs.get_value(&state)),
+                Op::Eql(lhs, rhs) => state.variable_store.insert(lhs.get_var(), if lhs.get_value(&state) == rhs.get_value(&state) { 1 } else { 0 }),
+            };
+            state.pc += 1;
+            states.push(state);
+        }
+        let res = if self.cache.contains_key(&states[states.len() - 1]) {
+            self.cache[&states[states.len() - 1]]
+        } else {
+            let c = 'z';
+            states[states.len() - 1].variable_store[&c] == 0
+        };
+        for state in states.iter().cloned() {
+            self.cache.insert(state, res);
+        }
+        res
+    }
+
+    pub fn run_no_cache(&self, inputs: &[i64]) -> i64 {
+        let mut var_store: HashMap<char, i64> = HashMap::new();
+        var_store.insert('w', 0);
+        var_store.insert('x', 0);
+        var_store.insert('y', 0);
+        var_store.insert('z', 0);
+        let mut state = State {
+            input_queue: LinkedList::from_iter(inputs.iter().cloned()),
+            variable_store: var_store,
+            pc: 0,
+        };
+        // println!("Input queue: {:?}", states[states.len() - 1].input_queue);
+        // println!("Program: {:?}", self.program);
+        while state.pc < self.program.len() {
+            // println!("Op {}: {:?}", state.pc, self.program[state.pc]);
+            match self.program[state.pc] {
+                Op::Inp(var) => state.variable_store.insert(var.get_var(), match state.input_queue.pop_front() {
+                    None => panic!("Requested input when exhausted!"),
+                    Some(inp) => inp,
+                }),
+                Op::Add(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) + rhs.get_value(&state)),
+                Op::Mul(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) * rhs.get_value(&state)),
+                Op::Div(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) / rhs.get_value(&state)),
+                Op::Mod(lhs, rhs) => state.variable_store.insert(lhs.get_var(), lhs.get_value(&state) % rhs.get_value(&state)),
+                Op::Eql(lhs, rhs) => state.variable_store.insert(lhs.get_var(), if lhs.get_value(&state) == rhs.get_value(&state) { 1 } else { 0 }),
+            };
+            state.pc += 1;
+        }
+        let c = 'z';
+        state.variable_store[&c]
+    }
+    
+    #[cfg(test)]
+    fn initialize(filename: &str) -> Self {
+        Self::parse(&aoc_common::read_file(filename)).expect("Invalid program")
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut program: Vec<Op> = Vec::new();
+        for line in aoc_common::lines(input) {
+            if line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let arity = if parts[0] == "inp" { 1 } else { 2 };
+            if parts.len() != arity + 1 {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("Expected {} operand(s)", arity),
+                ));
+            }
+            let ops: Vec<Operand> = parts[1..]
+                .iter()
+                .map(|x| Operand::parse(input, x))
+                .collect::<Result<Vec<Operand>, ParseError>>()?;
+            if let Operand::Constant(_) = ops[0] {
+                return Err(ParseError::at(input, parts[1], "Cannot assign to a constant"));
+            }
+            program.push(match parts[0] {
+                "inp" => Op::Inp(ops[0]),
+                "add" => Op::Add(ops[0], ops[1]),
+                "mul" => Op::Mul(ops[0], ops[1]),
+                "div" => Op::Div(ops[0], ops[1]),
+                "mod" => Op::Mod(ops[0], ops[1]),
+                "eql" => Op::Eql(ops[0], ops[1]),
+                _ => return Err(ParseError::at(input, parts[0], "Invalid op")),
+            });
+        }
+        Ok(Self {
+            program,
+            cache: HashMap::new(),
+        })
+    }
+
+    fn resolve_ast(&self, var_store: &HashMap<char, Rc<ASTNode>>, op: Operand) -> Rc<ASTNode> {
+        match op {
+            Operand::Constant(x) => Rc::new(ASTNode::Constant(x)),
+            Operand::Variable(c) => Rc::clone(&var_store[&c]),
+        }
+    }
+
+    fn range(&self, tree: &Rc<ASTNode>) -> Range {
+        match &**tree {
+            ASTNode::Constant(x) => Range{
+                low: Some(*x),
+                high: Some(*x),
+            },
+            ASTNode::Inp(_) => Range{
+                low: Some(1),
+                high: Some(9),
+            },
+            ASTNode::Add(lhs, rhs) => {
+                let lhs_range = self.range(lhs);
+                let rhs_range = self.range(rhs);
+                let low = match lhs_range.low {
+                    None => None,
+                    Some(lhs_low) => rhs_range.low.map(|rhs_low| lhs_low + rhs_low),
+                };
+                let high = match lhs_range.high {
+                    None => None,
+                    Some(lhs_high) => rhs_range.high.map(|rhs_high| lhs_high + rhs_high),
+                };
+                Range { low, high }
+            },
+            ASTNode::Mul(lhs, rhs) => {
+                let lhs_range = self.range(lhs);
+                let rhs_range = self.range(rhs);
+                // Mul is complicated. Only handle cases where one is a constant
+                match lhs_range.full_range() {
+                    None => (),
+                    Some((llow, lhigh)) => match rhs_range.full_range() {
+                        None => (),
+                        Some((rlow, rhigh)) if llow >= 0 && lhigh >= 0 && rlow >= 0 && rhigh >= 0 => {
+                            return Range{
+                                low: Some(llow * rlow),
+                                high: Some(lhigh * rhigh),
+                            };
+                        }
+                        Some(_) => (),
+                    }
+                }
+                match lhs_range.constant() {
+                    None => (),
+                    Some(c) => {
+                        let low = rhs_range.low.map(|low| low * c);
+                        let high = rhs_range.high.map(|high| high * c);
+                        if c >= 0 {
+                            return Range{ low, high }
+                        } else {
+                            return Range{ low: high, high: low }
+                        }
+                    }
+                }
+                match rhs_range.constant() {
+                    None => (),
+                    Some(c) => {
+                        let low = lhs_range.low.map(|low| low * c);
+                        let high = lhs_range.high.map(|high| high * c);
+                        if c >= 0 {
+                            return Range{ low, high }
+                        } else {
+                            return Range{ low: high, high: low }
+                        }
+                    }
+                }
+                Range { low: None, high: None }
+            },
+            ASTNode::Div(lhs, rhs) => {
+                // Only handle case where denominator is constant
+                let lhs_range = self.range(lhs);
+                let rhs_range = self.range(rhs);
+                match lhs_range.full_range() {
+                    None => (),
+                    Some((llow, lhigh)) => match rhs_range.full_range() {
+                        None => (),
+                        Some((rlow, rhigh)) if llow >= 0 && lhigh >= 0 && rlow >= 0 && rhigh >= 0 => {
+                            return Range{
+                                low: Some(llow / rhigh),
+                                high: Some(lhigh / rlow),
+                            };
+                        }
+                        Some(_) => (),
+                    }
+                }
+                match rhs_range.constant() {
+                    None => (),
+                    Some(c) => {
+                        let low = lhs_range.low.map(|low| low / c);
+                        let high = lhs_range.high.map(|high| high / c);
+                        if c >= 0 {
+                            return Range{ low, high }
+                        } else {
+                            return Range{ low: high, high: low }
+                        }
+                    }
+                }
+                Range { low: None, high: None }
+            },
+            ASTNode::Mod(lhs, rhs) => {
+                let lhs_range = self.range(lhs);
+                let rhs_range = self.range(rhs);
+                match rhs_range.constant() {
+                    None => (),
+                    Some(modulo) => match lhs_range.low {
+                        None => (),
+                        Some(low) => match lhs_range.high {
+                            None => (),
+                            Some(high) if high - low < modulo && high % modulo > low % modulo => {
+                                return Range{low: Some(low % modulo), high: Some(high % modulo)}
+                            }
+                            Some(_) => (),
+                        }
+                    }
+                }
+                Range{
+                    low: Some(0),
+                    high: rhs_range.high,
+                }
+            },
+            ASTNode::Eql(_, _) => {
+                Range{
+                    low: Some(0),
+                    high: Some(1),
+                }
+            },
+        }
+    }
+
+    fn eval_constants(&self, tree: Rc<ASTNode>) -> Rc<ASTNode> {
+        match &*tree {
+            ASTNode::Constant(_) => tree,
+            ASTNode::Inp(_) => tree,
+            ASTNode::Add(lhs, rhs) => {
+                // let lhs_new = self.eval_constants(Rc::clone(lhs));
+                // let rhs_new = self.eval_constants(Rc::clone(rhs));
+                let lhs_new = Rc::clone(lhs);
+                let rhs_new = Rc::clone(rhs);
+                if let ASTNode::Constant(x) = &*lhs_new {
+                    if *x == 0 {
+                        return rhs_new;
+                    }
+                }
+                if let ASTNode::Constant(x) = &*rhs_new {
+                    if *x == 0 {
+                        return lhs_new;
+                    }
+                    if let ASTNode::Constant(y) = &*lhs_new {
+                        let out = Rc::new(ASTNode::Constant(*y + *x));
+                        return out;
+                    }
+                }
+                tree
+                // Rc::new(ASTNode::Add(lhs_new, rhs_new))
+            },
+            ASTNode::Mul(lhs, rhs) => {
+                // let lhs_new = self.eval_constants(Rc::clone(lhs));
+                // let rhs_new = self.eval_constants(Rc::clone(rhs));
+                let lhs_new = Rc::clone(lhs);
+                let rhs_new = Rc::clone(rhs);
+                if let ASTNode::Constant(x) = &*lhs_new {
+                    if *x == 1 {
+                        return rhs_new;
+                    }
+                    if *x == 0 {
+                        return lhs_new;
+                    }
+                }
+                if let ASTNode::Constant(x) = &*rhs_new {
+                    if *x == 1 {
+                        return lhs_new;
+                    }
+                    if *x == 0 {
+                        return rhs_new;
+                    }
+                    if let ASTNode::Constant(y) = &*lhs_new {
+                        let out = Rc::new(ASTNode::Constant(*y * *x));
+                        return out;
+                    }
+                }
+                tree
+                // Rc::new(ASTNode::Mul(lhs_new, rhs_new))
+            },
+            ASTNode::Div(lhs, rhs) => {
+                // let lhs_new = self.eval_constants(Rc::clone(lhs));
+                // let rhs_new = self.eval_constants(Rc::clone(rhs));
+                let lhs_new = Rc::clone(lhs);
+                let rhs_new = Rc::clone(rhs);
+                if let ASTNode::Constant(x) = &*lhs_new {
+                    if *x == 0 {
+                        return lhs_new;
+                    }
+                }
+                if let ASTNode::Constant(x) = &*rhs_new {
+                    if *x == 1 {
+                        return lhs_new;
+                    }
+                    if let ASTNode::Constant(y) = &*lhs_new {
+                        let out = Rc::new(ASTNode::Constant(*y / *x));
+                        return out;
+                    }
+                }
+                tree
+                // Rc::new(ASTNode::Div(lhs_new, rhs_new))
+            },
+            ASTNode::Mod(lhs, rhs) => {
+                // let lhs_new = self.eval_constants(Rc::clone(lhs));
+                // let rhs_new = self.eval_constants(Rc::clone(rhs));
+                let lhs_new = Rc::clone(lhs);
+                let rhs_new = Rc::clone(rhs);
+                if let ASTNode::Constant(x) = &*rhs_new {
+                    if *x == 1 {
+                        let out = Rc::new(ASTNode::Constant(0));
+                        return out;
+                    }
+                    if let ASTNode::Constant(y) = &*lhs_new {
+                        let out = Rc::new(ASTNode::Constant(*y % *x));
+                        return out;
+                    }
+                }
+                tree
+                // Rc::new(ASTNode::Mod(lhs_new, rhs_new))
+            },
+            ASTNode::Eql(lhs, rhs) => {
+                // let lhs_new = self.eval_constants(Rc::clone(lhs));
+                // let rhs_new = self.eval_constants(Rc::clone(rhs));
+                let lhs_new = Rc::clone(lhs);
+                let rhs_new = Rc::clone(rhs);
+                if let ASTNode::Constant(x) = &*rhs_new {
+                    if let ASTNode::Constant(y) = &*lhs_new {
+                        let out = Rc::new(ASTNode::Constant(if *y == *x { 1 } else { 0 }));
+                        return out;
+                    }
+                }
+                let lhs_range = self.range(&lhs_new);
+                let rhs_range = self.range(&rhs_new);
+                if !lhs_range.can_intersect(&rhs_range) {
+                    let out = Rc::new(ASTNode::Constant(0));
+                    return out;
+                }
+                tree
+                // Rc::new(ASTNode::Eql(lhs_new, rhs_new))
+            },
+        }
+    }
+
+    /// Builds the expression for z, passing each step and the final tree to `log`.
+    pub fn simplify(&self, mut log: impl FnMut(fmt::Arguments)) -> Rc<ASTNode> {
+        let mut var_store: HashMap<char, Rc<ASTNode>> = HashMap::new();
+        let mut inp_number: u8 = 0;
+        let zero = Rc::new(ASTNode::Constant(0));
+        var_store.insert('w', Rc::clone(&zero));
+        var_store.insert('x', Rc::clone(&zero));
+        var_store.insert('y', Rc::clone(&zero));
+        var_store.insert('z', Rc::clone(&zero));
+        for op in self.program.iter() {
+            log(format_args!(":: {:?}", op));
+            let var = match op {
+                Op::Inp(var) => var.get_var(),
+                Op::Add(lhs, _) => lhs.get_var(),
+                Op::Mul(lhs, _) => lhs.get_var(),
+                Op::Div(lhs, _) => lhs.get_var(),
+                Op::Mod(lhs, _) => lhs.get_var(),
+                Op::Eql(lhs, _) => lhs.get_var(),
+            };
+            match op {
+                Op::Inp(var) => {
+                    var_store.insert(var.get_var(), Rc::new(ASTNode::Inp(inp_number)));
+                    inp_number += 1;
+                },
+                Op::Add(lhs, rhs) => {
+                    var_store.insert(lhs.get_var(), self.eval_constants(Rc::new(ASTNode::Add(
+                            self.resolve_ast(&var_store, *lhs),
+                            self.resolve_ast(&var_store, *rhs),
+                    ))));
+                },
+                Op::Mul(lhs, rhs) => {
+                    var_store.insert(lhs.get_var(), self.eval_constants(Rc::new(ASTNode::Mul(
+                            self.resolve_ast(&var_store, *lhs),
+                            self.resolve_ast(&var_store, *rhs),
+                    ))));
+                },
+                Op::Div(lhs, rhs) => {
+                    var_store.insert(lhs.get_var(), self.eval_constants(Rc::new(ASTNode::Div(
+                            self.resolve_ast(&var_store, *lhs),
+                            self.resolve_ast(&var_store, *rhs),
+                    ))));
+                },
+                Op::Mod(lhs, rhs) => {
+                    var_store.insert(lhs.get_var(), self.eval_constants(Rc::new(ASTNode::Mod(
+                            self.resolve_ast(&var_store, *lhs),
+                            self.resolve_ast(&var_store, *rhs),
+                    ))));
+                },
+                Op::Eql(lhs, rhs) => {
+                    var_store.insert(lhs.get_var(), self.eval_constants(Rc::new(ASTNode::Eql(
+                            self.resolve_ast(&var_store, *lhs),
+                            self.resolve_ast(&var_store, *rhs),
+                    ))));
+                },
+            };
+            let mut tree = String::new();
+            ASTNode::render(&var_store[&var], 0, 5, &mut tree);
+            log(format_args!("  variable {} is:\n{}", var, tree));
+            log(format_args!("  with range {:?}:\n", self.range(&var_store[&var])));
+        }
+        let mut ast = var_store[&'z'].clone();
+        ast = self.eval_constants(ast);
+        // ast = ASTNode::replace_common(&ast);
+        let mut tree = String::new();
+        ASTNode::render(&ast, 0, 15, &mut tree);
+        log(format_args!("{}", tree));
+        log(format_args!("Ops: {}", ASTNode::ops(&ast)));
+        log(format_args!("Inputs used: {:?}", ASTNode::inputs_used(&ast)));
+        ast
+        // println!("AST: {:?}", ast);
+    }
+}
+
+/// Index of an ALU variable in the `[w, x, y, z]` register file.
+fn register(var: char) -> usize {
+    match var {
+        'w' => 0,
+        'x' => 1,
+        'y' => 2,
+        'z' => 3,
+        _ => panic!("Unknown variable {}", var),
+    }
+}
+
+fn operand_source(operand: Operand) -> String {
+    match operand {
+        Operand::Constant(c) => c.to_string(),
+        Operand::Variable(v) => v.to_string(),
+    }
+}
+
+/// The instruction as it appears in a program, like `add x 10`.
+fn instruction(op: &Op) -> String {
+    let (name, lhs, rhs) = match *op {
+        Op::Inp(var) => return format!("inp {}", var.get_var()),
+        Op::Add(lhs, rhs) => ("add", lhs, rhs),
+        Op::Mul(lhs, rhs) => ("mul", lhs, rhs),
+        Op::Div(lhs, rhs) => ("div", lhs, rhs),
+        Op::Mod(lhs, rhs) => ("mod", lhs, rhs),
+        Op::Eql(lhs, rhs) => ("eql", lhs, rhs),
+    };
+    format!("{} {} {}", name, lhs.get_var(), operand_source(rhs))
+}
+
+type Step = Box<dyn Fn(&mut [i64; 4], &mut dyn Iterator<Item = i64>)>;
+
+/// A program lowered to one closure per instruction, run in-process.
+pub struct CompiledProgram {
+    steps: Vec<Step>,
+}
+
+impl CompiledProgram {
+    /// Runs the program on `inputs` and returns the final value of z.
+    pub fn run(&self, inputs: &[i64]) -> i64 {
+        let mut registers = [0; 4];
+        let mut inputs = inputs.iter().copied();
+        for step in self.steps.iter() {
+            step(&mut registers, &mut inputs);
+        }
+        registers[3]
+    }
+}
+
+fn compile_binary(lhs: Operand, rhs: Operand, f: fn(i64, i64) -> i64) -> Step {
+    let dst = register(lhs.get_var());
+    match rhs {
+        Operand::Constant(c) => Box::new(move |registers, _| registers[dst] = f(registers[dst], c)),
+        Operand::Variable(v) => {
+            let src = register(v);
+            Box::new(move |registers, _| registers[dst] = f(registers[dst], registers[src]))
+        }
+    }
+}
+
+impl SmartComputer {
+    /// Emits C defining `int run_program(const int64_t* inputs)`, true when z ends at 0.
+    pub fn to_c(&self) -> String {
+        let inputs = self
+            .program
+            .iter()
+            .filter(|op| matches!(op, Op::Inp(_)))
+            .count();
+        let mut lines: Vec<String> = vec![
+            "#include <stdint.h>".to_string(),
+            "".to_string(),
+            "int run_program(const int64_t* inputs) {".to_string(),
+            "  int64_t w, x, y, z;".to_string(),
+            "  w = x = y = z = 0;".to_string(),
+        ];
+        for i in 0..inputs {
+            lines.push(format!("  const int64_t input{} = inputs[{}];", i, i));
+        }
+        let mut next_input = 0;
+        for op in self.program.iter() {
+            lines.push(match *op {
+                Op::Inp(var) => {
+                    next_input += 1;
+                    format!("  {} = input{};", var.get_var(), next_input - 1)
+                }
+                Op::Add(lhs, rhs) => format!("  {} += {};", lhs.get_var(), operand_source(rhs)),
+                Op::Mul(lhs, rhs) => format!("  {} *= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Div(lhs, rhs) => format!("  {} /= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Mod(lhs, rhs) => format!("  {} %= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Eql(lhs, rhs) => {
+                    let var = lhs.get_var();
+                    format!("  {} = ({} == {});", var, var, operand_source(rhs))
+                }
+            });
+        }
+        lines.push("  return z == 0;".to_string());
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    /// Emits Rust defining `pub fn run_program(inputs: &[i64]) -> i64`, which returns the final z.
+    pub fn to_rust(&self) -> String {
+        let mut lines: Vec<String> = vec![
+            "#[allow(unused_mut, unused_assignments, unused_variables)]".to_string(),
+            "pub fn run_program(inputs: &[i64]) -> i64 {".to_string(),
+            "    let (mut w, mut x, mut y, mut z): (i64, i64, i64, i64) = (0, 0, 0, 0);"
+                .to_string(),
+        ];
+        let mut next_input = 0;
+        for op in self.program.iter() {
+            lines.push(match *op {
+                Op::Inp(var) => {
+                    next_input += 1;
+                    format!("    {} = inputs[{}];", var.get_var(), next_input - 1)
+                }
+                Op::Add(lhs, rhs) => format!("    {} += {};", lhs.get_var(), operand_source(rhs)),
+                Op::Mul(lhs, rhs) => format!("    {} *= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Div(lhs, rhs) => format!("    {} /= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Mod(lhs, rhs) => format!("    {} %= {};", lhs.get_var(), operand_source(rhs)),
+                Op::Eql(lhs, rhs) => {
+                    let var = lhs.get_var();
+                    format!("    {} = ({} == {}) as i64;", var, var, operand_source(rhs))
+                }
+            });
+        }
+        lines.push("    z".to_string());
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    /// Lowers the program to closures, skipping the interpreter's per-op dispatch.
+    pub fn compile(&self) -> CompiledProgram {
+        let steps = self
+            .program
+            .iter()
+            .map(|op| -> Step {
+                match *op {
+                    Op::Inp(var) => {
+                        let dst = register(var.get_var());
+                        Box::new(move |registers, inputs| {
+                            registers[dst] = inputs.next().expect("Requested input when exhausted!")
+                        })
+                    }
+                    Op::Add(lhs, rhs) => compile_binary(lhs, rhs, |a, b| a + b),
+                    Op::Mul(lhs, rhs) => compile_binary(lhs, rhs, |a, b| a * b),
+                    Op::Div(lhs, rhs) => compile_binary(lhs, rhs, |a, b| a / b),
+                    Op::Mod(lhs, rhs) => compile_binary(lhs, rhs, |a, b| a % b),
+                    Op::Eql(lhs, rhs) => compile_binary(lhs, rhs, |a, b| (a == b) as i64),
+                }
+            })
+            .collect();
+        CompiledProgram { steps }
+    }
+}
+
+fn digits_to_number(digits: &[i64]) -> i64 {
+    let mut out: i64 = 0;
+    for digit in digits {
+        out = (out * 10) + digit;
+    }
+    out
+}
+
+/// One of MONAD's input blocks; `_` stands for the constant that differs between blocks.
+const MONAD_BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y _",
+    "mul y x", "add z y",
+];
+
+fn not_monad(reason: impl fmt::Display) -> SolveError {
+    SolveError::new(format!("not a MONAD program: {}", reason))
+}
+
+/// Reads the `(div z, add x, add y)` constants out of each of MONAD's 18-op input blocks.
+fn monad_blocks(program: &[Op]) -> Result<Vec<(i64, i64, i64)>, SolveError> {
+    if program.is_empty() || !program.len().is_multiple_of(MONAD_BLOCK.len()) {
+        return Err(not_monad(format!(
+            "{} instructions don't split into blocks of {}",
+            program.len(),
+            MONAD_BLOCK.len()
+        )));
+    }
+    let mut blocks: Vec<(i64, i64, i64)> = Vec::new();
+    for (b, block) in program.chunks(MONAD_BLOCK.len()).enumerate() {
+        let mut constants: Vec<i64> = Vec::new();
+        for (k, (op, expected)) in block.iter().zip(MONAD_BLOCK).enumerate() {
+            let found = instruction(op);
+            let matches = match expected.strip_suffix('_') {
+                Some(prefix) => match found.strip_prefix(prefix).map(str::parse::<i64>) {
+                    Some(Ok(c)) => {
+                        constants.push(c);
+                        true
+                    }
+                    _ => false,
+                },
+                None => found == expected,
+            };
+            if !matches {
+                return Err(not_monad(format!(
+                    "instruction {} is `{}`, expected `{}`",
+                    b * MONAD_BLOCK.len() + k + 1,
+                    found,
+                    expected
+                )));
+            }
+        }
+        if constants[0] != 1 && constants[0] != 26 {
+            return Err(not_monad(format!(
+                "block {} divides z by {}, expected 1 or 26",
+                b + 1,
+                constants[0]
+            )));
+        }
+        blocks.push((constants[0], constants[1], constants[2]));
+    }
+    Ok(blocks)
+}
+
+/// Finds the largest or smallest model number MONAD accepts.
+///
+/// Each block either pushes `w + add y` onto z (a base-26 stack) or pops it,
+/// and z only ends at 0 if every pop's digit equals the popped value plus
+/// its `add x`. That pairs the digits up, so each pair can be chosen greedily.
+fn model_number(computer: &SmartComputer, largest: bool) -> Result<i64, SolveError> {
+    let blocks = monad_blocks(&computer.program)?;
+    let mut digits: Vec<i64> = vec![0; blocks.len()];
+    let mut stack: Vec<(usize, i64)> = Vec::new();
+    for (i, (div, add_x, add_y)) in blocks.into_iter().enumerate() {
+        if div == 1 {
+            stack.push((i, add_y));
+            continue;
+        }
+        let Some((j, pushed)) = stack.pop() else {
+            return Err(not_monad(format!("block {} pops an empty stack", i + 1)));
+        };
+        // digits[i] == digits[j] + delta
+        let delta = pushed + add_x;
+        digits[j] = if largest {
+            cmp::min(9, 9 - delta)
+        } else {
+            cmp::max(1, 1 - delta)
+        };
+        digits[i] = digits[j] + delta;
+    }
+    if !stack.is_empty() {
+        return Err(not_monad("it pushes more blocks than it pops"));
+    }
+    if digits.iter().any(|digit| !(1..=9).contains(digit)) {
+        return Err(SolveError::new("no model number is valid"));
+    }
+    let number = digits_to_number(&digits);
+    if computer.compile().run(&digits) != 0 {
+        return Err(SolveError::new(format!("MONAD rejects {}", number)));
+    }
+    Ok(number)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Input = SmartComputer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        SmartComputer::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(model_number(input, true)?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(model_number(input, false).map(|number| number.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn factor_of_3() {
+        let mut computer = crate::SmartComputer::initialize("example.txt");
+        assert!(!computer.run(39, 2));
+        // With cache:
+        assert!(!computer.run(39, 2));
+        assert!(computer.run(38, 2));
+        assert!(!computer.run(26, 2));
+        assert!(!computer.run(13, 2));
+        assert!(computer.run(12, 2));
+        assert!(!computer.run(00, 2));
+    }
+
+    #[test]
+    fn compiled_matches_interpreter() {
+        let computer = crate::SmartComputer::initialize("example2.txt");
+        let compiled = computer.compile();
+        for n in 0..16 {
+            assert_eq!(compiled.run(&[n]), computer.run_no_cache(&[n]));
+        }
+        let monad = crate::SmartComputer::initialize("input.txt");
+        let digits = [9, 6, 2, 9, 9, 8, 9, 6, 4, 4, 9, 9, 9, 7];
+        assert_eq!(monad.compile().run(&digits), 0);
+        assert_ne!(monad.compile().run(&[1; 14]), 0);
+    }
+
+    #[test]
+    fn emits_source() {
+        let computer = crate::SmartComputer::initialize("example.txt");
+        assert_eq!(
+            computer.to_c(),
+            "#include <stdint.h>\n\nint run_program(const int64_t* inputs) {\n  \
+             int64_t w, x, y, z;\n  w = x = y = z = 0;\n  \
+             const int64_t input0 = inputs[0];\n  const int64_t input1 = inputs[1];\n  \
+             z = input0;\n  x = input1;\n  z *= 3;\n  z = (z == x);\n  return z == 0;\n}\n"
+        );
+        assert_eq!(
+            computer.to_rust(),
+            "#[allow(unused_mut, unused_assignments, unused_variables)]\n\
+             pub fn run_program(inputs: &[i64]) -> i64 {\n    \
+             let (mut w, mut x, mut y, mut z): (i64, i64, i64, i64) = (0, 0, 0, 0);\n    \
+             z = inputs[0];\n    x = inputs[1];\n    z *= 3;\n    z = (z == x) as i64;\n    z\n}\n"
+        );
+    }
+
+    #[test]
+    fn rejects_other_programs() {
+        let computer = crate::SmartComputer::initialize("example.txt");
+        assert_eq!(
+            crate::model_number(&computer, true).unwrap_err().to_string(),
+            "not a MONAD program: 4 instructions don't split into blocks of 18"
+        );
+
+        let mut monad = crate::SmartComputer::initialize("input.txt");
+        monad.program[18 * 3 + 15] = crate::Op::Add(
+            crate::Operand::Variable('y'),
+            crate::Operand::Variable('w'),
+        );
+        assert_eq!(
+            crate::model_number(&monad, false).unwrap_err().to_string(),
+            "not a MONAD program: instruction 70 is `add y w`, expected `add y _`"
+        );
+        monad.program.truncate(18);
+        assert_eq!(
+            crate::model_number(&monad, false).unwrap_err().to_string(),
+            "not a MONAD program: it pushes more blocks than it pops"
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let mut computer = crate::SmartComputer::initialize("example2.txt");
+        assert!(!computer.run(9, 1));
+        assert!(computer.run(8, 1));
+        assert!(!computer.run(7, 1));
+        assert!(computer.run(6, 1));
+    }
+}
+
@@ -0,0 +1,179 @@
+use aoc_common::{Answer, Grid, ParseError, Solution, SolveError};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+fn parse(input: &str) -> Result<Grid<Cell>, ParseError> {
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let mut row: Vec<Cell> = Vec::new();
+        for (i, c) in line.char_indices() {
+            row.push(match c {
+                '.' => Cell::Empty,
+                '>' => Cell::East,
+                'v' => Cell::South,
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        "Expected '>', 'v' or '.'",
+                    ))
+                }
+            });
+        }
+        if !rows.is_empty() && row.len() != rows[0].len() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Row length differs from the first row",
+            ));
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(ParseError::at_end(input, "Empty map"));
+    }
+    Ok(Grid::from_rows(rows).with_wrapping(true))
+}
+
+pub fn render(grid: &Grid<Cell>) -> String {
+    grid.render(|cell| match cell {
+        Cell::Empty => '.',
+        Cell::East => '>',
+        Cell::South => 'v',
+    })
+}
+
+/// Moves every cucumber of `herd` one cell along `delta` if that cell was empty.
+fn move_herd(grid: &Grid<Cell>, herd: Cell, delta: (isize, isize)) -> (Grid<Cell>, bool) {
+    let mut next = grid.clone();
+    let mut moved = false;
+    for pos in grid.positions() {
+        if grid[pos] != herd {
+            continue;
+        }
+        let target = grid.offset(pos, delta).expect("The sea floor wraps");
+        if grid[target] == Cell::Empty {
+            next[pos] = Cell::Empty;
+            next[target] = herd;
+            moved = true;
+        }
+    }
+    (next, moved)
+}
+
+/// One step: the east-facing herd moves, then the south-facing one.
+fn step(grid: &Grid<Cell>) -> (Grid<Cell>, bool) {
+    let (east, moved_east) = move_herd(grid, Cell::East, (0, 1));
+    let (south, moved_south) = move_herd(&east, Cell::South, (1, 0));
+    (south, moved_east || moved_south)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing moved on this step, counting from 1.
+    Settled(usize),
+    /// The herds never stop: the map after step `start` recurs every `period` steps.
+    Cycles { start: usize, period: usize },
+}
+
+impl Outcome {
+    /// The part 1 answer: the settling step, or an error for an endless herd.
+    pub fn answer(&self) -> Answer {
+        match *self {
+            Outcome::Settled(steps) => Ok(steps.to_string()),
+            Outcome::Cycles { start, period } => Err(SolveError::new(format!(
+                "never settles: repeats every {} steps from step {}",
+                period, start
+            ))),
+        }
+    }
+}
+
+/// Steps until nothing moves, calling `on_frame` with the map after every step (and step 0).
+pub fn simulate(initial: &Grid<Cell>, mut on_frame: impl FnMut(usize, &Grid<Cell>)) -> Outcome {
+    let mut seen: HashMap<Grid<Cell>, usize> = HashMap::new();
+    let mut grid = initial.clone();
+    let mut steps = 0;
+    on_frame(steps, &grid);
+    loop {
+        if let Some(start) = seen.insert(grid.clone(), steps) {
+            return Outcome::Cycles {
+                start,
+                period: steps - start,
+            };
+        }
+        let (next, moved) = step(&grid);
+        steps += 1;
+        if !moved {
+            return Outcome::Settled(steps);
+        }
+        grid = next;
+        on_frame(steps, &grid);
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Grid<Cell>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        simulate(input, |_, _| {}).answer()
+    }
+
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settles_example() {
+        let grid = parse(include_str!("../example.txt")).unwrap();
+        let mut frames = 0;
+        assert_eq!(simulate(&grid, |_, _| frames += 1), Outcome::Settled(58));
+        assert_eq!(frames, 58);
+    }
+
+    #[test]
+    fn detects_endless_herd() {
+        // part1.py never terminates on this map.
+        let grid = parse(include_str!("../simple.txt")).unwrap();
+        assert_eq!(
+            simulate(&grid, |_, _| ()),
+            Outcome::Cycles {
+                start: 4,
+                period: 11
+            }
+        );
+        assert_eq!(
+            Day25::part1(&grid).unwrap_err().to_string(),
+            "never settles: repeats every 11 steps from step 4"
+        );
+    }
+
+    #[test]
+    fn steps_east_then_south() {
+        let grid = parse("..........\n.>v....v..\n.......>..\n..........\n").unwrap();
+        let (next, moved) = step(&grid);
+        assert!(moved);
+        assert_eq!(
+            render(&next),
+            "..........\n.>........\n..v....v>.\n..........\n"
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use aoc_common::runner::{self, ExtraArgs, Options};
+use aoc_common::Solution;
+use day25::Day25;
+
+/// Solves part 1 while printing every intermediate map to stderr.
+fn frames(options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let grid = Day25::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    let outcome = day25::simulate(&grid, |steps, grid| {
+        eprintln!("After {} steps:\n{}", steps, day25::render(grid));
+    });
+    match outcome.answer() {
+        Ok(answer) => println!("Part 1: {}", answer),
+        Err(err) => return Err(format!("error: part 1: {}", err)),
+    }
+    Ok(())
+}
+
+fn main() {
+    // `--frames` prints every intermediate map to stderr.
+    let mut args = ExtraArgs::from_env::<Day25>("[--frames]");
+    if args.take_switch("--frames") {
+        args.run_mode(frames);
+    } else {
+        args.run_answers::<Day25>();
+    }
+}
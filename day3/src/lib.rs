@@ -0,0 +1,524 @@
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct Reading {
+    digits: Vec<u8>,
+}
+
+impl Reading {
+    #[cfg(test)]
+    fn to_decimal(&self) -> u64 {
+        let mut value: u64 = 0;
+        for i in 0..self.digits.len() {
+            let shift: u64 = (self.digits.len() - i - 1).try_into().unwrap();
+            let digit: u64 = self.digits[i].into();
+            value |= digit << shift;
+        }
+        value
+    }
+
+    /// The value as little-endian base 2^32 limbs, for any width.
+    fn limbs(&self) -> Vec<u64> {
+        let mut limbs: Vec<u64> = vec![0; self.digits.len() / 32 + 1];
+        for (i, digit) in self.digits.iter().rev().enumerate() {
+            limbs[i / 32] |= (*digit as u64) << (i % 32);
+        }
+        limbs
+    }
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in self.digits.iter() {
+            write!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+/// The product of two readings' values in decimal, however wide they are.
+fn decimal_product(a: &Reading, b: &Reading) -> String {
+    let (x, y) = (a.limbs(), b.limbs());
+    let mut product: Vec<u64> = vec![0; x.len() + y.len()];
+    for i in 0..x.len() {
+        let mut carry: u64 = 0;
+        for j in 0..y.len() {
+            let t = product[i + j] + x[i] * y[j] + carry;
+            product[i + j] = t & 0xffff_ffff;
+            carry = t >> 32;
+        }
+        product[i + y.len()] = carry;
+    }
+
+    // Peel off base 10^9 chunks, least significant first.
+    let mut chunks: Vec<u64> = Vec::new();
+    while product.iter().any(|limb| *limb != 0) {
+        let mut rem: u64 = 0;
+        for limb in product.iter_mut().rev() {
+            let cur = (rem << 32) | *limb;
+            *limb = cur / 1_000_000_000;
+            rem = cur % 1_000_000_000;
+        }
+        chunks.push(rem);
+    }
+    match chunks.split_last() {
+        None => "0".to_string(),
+        Some((first, rest)) => {
+            let mut out = first.to_string();
+            for chunk in rest.iter().rev() {
+                out.push_str(&format!("{:09}", chunk));
+            }
+            out
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Reading>, ParseError> {
+    let mut out: Vec<Reading> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let mut row: Vec<u8> = Vec::new();
+        for (i, c) in line.char_indices() {
+            let x: u8 = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => {
+                    let token = &line[i..i + c.len_utf8()];
+                    return Err(ParseError::at(input, token, "Invalid character"));
+                }
+            };
+            row.push(x);
+        }
+        if !out.is_empty() && row.len() != out[0].digits.len() {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Reading width differs from the first reading",
+            ));
+        }
+        out.push(Reading { digits: row });
+    }
+    if out.is_empty() {
+        return Err(ParseError::at_end(input, "No readings"));
+    }
+    Ok(out)
+}
+
+fn ones_counts(readings: &[Reading]) -> Vec<u64> {
+    let mut ones: Vec<u64> = vec![0; readings[0].digits.len()];
+    for reading in readings {
+        for d in 0..reading.digits.len() {
+            if reading.digits[d] == 1 {
+                ones[d] += 1;
+            }
+        }
+    }
+    ones
+}
+
+fn gamma_rate(readings: &[Reading]) -> Reading {
+    let ones = ones_counts(readings);
+    let n: u64 = readings.len().try_into().unwrap();
+    let mut new_reading_digits: Vec<u8> = Vec::new();
+    for i in 0..ones.len() {
+        new_reading_digits.push(if ones[i] > (n - ones[i]) { 1 } else { 0 })
+    }
+    Reading {
+        digits: new_reading_digits,
+    }
+}
+
+fn epsilon_rate(readings: &[Reading]) -> Reading {
+    let ones = ones_counts(readings);
+    let n: u64 = readings.len().try_into().unwrap();
+    let mut new_reading_digits: Vec<u8> = Vec::new();
+    for i in 0..ones.len() {
+        new_reading_digits.push(if ones[i] <= (n - ones[i]) { 1 } else { 0 })
+    }
+    Reading {
+        digits: new_reading_digits,
+    }
+}
+
+/// Binary trie over the readings, one level per bit; node 0 is the root.
+struct Trie {
+    /// Child node for a 0 and a 1 bit; 0 means no child, as the root is nobody's child.
+    children: Vec<[usize; 2]>,
+    /// Number of readings passing through each node.
+    counts: Vec<usize>,
+    width: usize,
+}
+
+impl Trie {
+    fn new(readings: &[Reading]) -> Trie {
+        let mut trie = Trie {
+            children: vec![[0, 0]],
+            counts: vec![readings.len()],
+            width: readings[0].digits.len(),
+        };
+        for reading in readings {
+            let mut node = 0;
+            for digit in reading.digits.iter() {
+                let bit = *digit as usize;
+                if trie.children[node][bit] == 0 {
+                    trie.children[node][bit] = trie.children.len();
+                    trie.children.push([0, 0]);
+                    trie.counts.push(0);
+                }
+                node = trie.children[node][bit];
+                trie.counts[node] += 1;
+            }
+        }
+        trie
+    }
+
+    fn count(&self, node: usize, bit: usize) -> usize {
+        match self.children[node][bit] {
+            0 => 0,
+            child => self.counts[child],
+        }
+    }
+
+    /// Follows the bit criteria for `rating` from the root to a leaf.
+    ///
+    /// Once one reading is left every step has a single branch, so this ends
+    /// on the same reading as filtering a list would.
+    fn walk(&self, rating: Rating) -> Walk {
+        let mut node = 0;
+        let mut digits: Vec<u8> = Vec::with_capacity(self.width);
+        let mut ties: Vec<usize> = Vec::new();
+        for column in 0..self.width {
+            let (zeros, ones) = (self.count(node, 0), self.count(node, 1));
+            if zeros == ones {
+                ties.push(column);
+            }
+            let bit = if zeros == 0 || ones == 0 {
+                if ones > 0 {
+                    1
+                } else {
+                    0
+                }
+            } else if (rating == Rating::Oxygen) == (ones >= zeros) {
+                1
+            } else {
+                0
+            };
+            digits.push(bit as u8);
+            node = self.children[node][bit];
+        }
+        Walk {
+            reading: Reading { digits },
+            remaining: self.counts[node],
+            ties,
+        }
+    }
+
+    fn rating(&self, rating: Rating) -> Result<Reading, RatingError> {
+        let walk = self.walk(rating);
+        if walk.remaining > 1 {
+            return Err(RatingError::Ambiguous {
+                rating,
+                reading: walk.reading.to_string(),
+                count: walk.remaining,
+            });
+        }
+        Ok(walk.reading)
+    }
+
+    /// Every reading that occurs more than once, with its count, in sorted order.
+    fn duplicates(&self) -> Vec<(String, usize)> {
+        let mut out: Vec<(String, usize)> = Vec::new();
+        let mut stack: Vec<(usize, String)> = vec![(0, String::new())];
+        while let Some((node, prefix)) = stack.pop() {
+            if prefix.len() == self.width {
+                if self.counts[node] > 1 {
+                    out.push((prefix, self.counts[node]));
+                }
+                continue;
+            }
+            for bit in [1, 0] {
+                let child = self.children[node][bit];
+                if child != 0 {
+                    stack.push((child, format!("{}{}", prefix, bit)));
+                }
+            }
+        }
+        out
+    }
+}
+
+struct Walk {
+    reading: Reading,
+    /// How many readings equal `reading`.
+    remaining: usize,
+    /// Columns where both bits were equally common among the candidates.
+    ties: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Oxygen,
+    Co2,
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rating::Oxygen => write!(f, "oxygen generator"),
+            Rating::Co2 => write!(f, "CO2 scrubber"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RatingError {
+    /// Filtering ended on several copies of the same reading.
+    Ambiguous {
+        rating: Rating,
+        reading: String,
+        count: usize,
+    },
+}
+
+impl fmt::Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RatingError::Ambiguous {
+                rating,
+                reading,
+                count,
+            } => write!(
+                f,
+                "{} rating is ambiguous: {} copies of {} remain",
+                rating, count, reading
+            ),
+        }
+    }
+}
+
+impl Error for RatingError {}
+
+/// Where the puzzle's tie-breaking rules decided an answer.
+///
+/// Columns are counted from 0 at the leftmost (most significant) bit.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Columns where ones and zeros are equally common across all readings.
+    pub tied_columns: Vec<usize>,
+    /// Columns where the oxygen filter saw a tie among its remaining candidates.
+    pub oxygen_ties: Vec<usize>,
+    /// Columns where the CO2 filter saw a tie among its remaining candidates.
+    pub co2_ties: Vec<usize>,
+    /// Readings that occur more than once, with their counts.
+    pub duplicates: Vec<(String, usize)>,
+    pub oxygen: Result<String, RatingError>,
+    pub co2: Result<String, RatingError>,
+}
+
+impl Diagnostics {
+    pub fn new(readings: &[Reading]) -> Diagnostics {
+        let n = readings.len() as u64;
+        let tied_columns = ones_counts(readings)
+            .iter()
+            .enumerate()
+            .filter(|(_, ones)| **ones * 2 == n)
+            .map(|(column, _)| column)
+            .collect();
+        let trie = Trie::new(readings);
+        Diagnostics {
+            tied_columns,
+            oxygen_ties: trie.walk(Rating::Oxygen).ties,
+            co2_ties: trie.walk(Rating::Co2).ties,
+            duplicates: trie.duplicates(),
+            oxygen: trie.rating(Rating::Oxygen).map(|r| r.to_string()),
+            co2: trie.rating(Rating::Co2).map(|r| r.to_string()),
+        }
+    }
+}
+
+fn columns(columns: &[usize]) -> String {
+    match columns.len() {
+        0 => "none".to_string(),
+        _ => columns
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>()
+            .join(", "),
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Tied columns: {} (gamma takes 0 and epsilon takes 1)",
+            columns(&self.tied_columns)
+        )?;
+        writeln!(
+            f,
+            "Oxygen filter ties: {} (kept 1)",
+            columns(&self.oxygen_ties)
+        )?;
+        writeln!(f, "CO2 filter ties: {} (kept 0)", columns(&self.co2_ties))?;
+        if self.duplicates.is_empty() {
+            writeln!(f, "Duplicate readings: none")?;
+        } else {
+            let listed: Vec<String> = self
+                .duplicates
+                .iter()
+                .map(|(reading, count)| format!("{} x{}", reading, count))
+                .collect();
+            writeln!(f, "Duplicate readings: {}", listed.join(", "))?;
+        }
+        for (name, rating) in [("Oxygen", &self.oxygen), ("CO2", &self.co2)] {
+            match rating {
+                Ok(reading) => writeln!(f, "{} rating: {}", name, reading)?,
+                Err(err) => writeln!(f, "{} rating: error: {}", name, err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn oxy_co2_rate(readings: &[Reading], oxy: bool) -> u64 {
+    let mut mask: Vec<bool> = vec![true; readings.len()];
+    for i in 0..readings[0].digits.len() {
+        let mut ones: usize = 0;
+        let mut count: usize = 0;
+        for j in 0..readings.len() {
+            if mask[j] {
+                if readings[j].digits[i] == 1 {
+                    ones += 1;
+                }
+                count += 1;
+            }
+        }
+        let keep: u8 = {
+            let val = if ones >= (count - ones) { 1 } else { 0 };
+            if oxy {
+                val
+            } else {
+                1 - val
+            }
+        };
+        count = 0;
+        let mut found_index: usize = 0;
+        for j in 0..readings.len() {
+            if mask[j] {
+                if readings[j].digits[i] == keep {
+                    count += 1;
+                    found_index = j;
+                } else {
+                    mask[j] = false;
+                }
+            }
+        }
+        if count == 1 {
+            return readings[found_index].to_decimal();
+        }
+    }
+    panic!("No definitive solution");
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Reading>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(decimal_product(&gamma_rate(input), &epsilon_rate(input)))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let trie = Trie::new(input);
+        let life_support = || -> Result<String, RatingError> {
+            let oxygen = trie.rating(Rating::Oxygen)?;
+            Ok(decimal_product(&oxygen, &trie.rating(Rating::Co2)?))
+        };
+        Some(life_support().map_err(SolveError::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(bits: &str) -> Reading {
+        Reading {
+            digits: bits.bytes().map(|b| b - b'0').collect(),
+        }
+    }
+
+    #[test]
+    fn trie_matches_rescanning() {
+        let readings = parse_input(include_str!("../example.txt")).unwrap();
+        let trie = Trie::new(&readings);
+        for (rating, oxy) in [(Rating::Oxygen, true), (Rating::Co2, false)] {
+            assert_eq!(
+                trie.rating(rating).unwrap().to_decimal(),
+                oxy_co2_rate(&readings, oxy)
+            );
+        }
+    }
+
+    #[test]
+    fn multiplies_wide_readings() {
+        let big = reading(&format!("1{}", "0".repeat(69)));
+        assert_eq!(
+            decimal_product(&big, &reading("1")),
+            "590295810358705651712"
+        );
+        assert_eq!(
+            decimal_product(&big, &big),
+            "348449143727040986586495598010130648530944"
+        );
+        assert_eq!(decimal_product(&reading("000"), &big), "0");
+    }
+
+    #[test]
+    fn rates_wide_readings() {
+        let wide = |prefix: &str| format!("{}{}", prefix, "0".repeat(68));
+        let input = [wide("11"), wide("10"), wide("01")].join("\n");
+        let readings = parse_input(&input).unwrap();
+        let trie = Trie::new(&readings);
+        let oxygen = trie.rating(Rating::Oxygen).unwrap();
+        assert_eq!(oxygen.digits, reading(&wide("11")).digits);
+        let co2 = trie.rating(Rating::Co2).unwrap();
+        assert_eq!(co2.digits, reading(&wide("01")).digits);
+    }
+
+    #[test]
+    fn diagnoses_ties() {
+        let readings = parse_input("110\n011\n101\n011\n").unwrap();
+        let diagnostics = Diagnostics::new(&readings);
+        assert_eq!(diagnostics.tied_columns, vec![0]);
+        assert_eq!(diagnostics.oxygen_ties, vec![0, 1]);
+        assert_eq!(diagnostics.co2_ties, vec![0]);
+        assert_eq!(diagnostics.duplicates, vec![("011".to_string(), 2)]);
+        assert_eq!(diagnostics.oxygen, Ok("110".to_string()));
+        assert_eq!(
+            diagnostics.co2,
+            Err(RatingError::Ambiguous {
+                rating: Rating::Co2,
+                reading: "011".to_string(),
+                count: 2,
+            })
+        );
+        let err = Day3::part2(&readings).unwrap().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RatingError>(),
+            diagnostics.co2.as_ref().err()
+        );
+        assert_eq!(
+            err.to_string(),
+            "CO2 scrubber rating is ambiguous: 2 copies of 011 remain"
+        );
+    }
+}
@@ -0,0 +1,496 @@
+use aoc_common::rng::Rng;
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+use std::collections::HashMap;
+
+/// Which completed patterns win a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Any full row or column.
+    pub lines: bool,
+    /// Either full diagonal; only square boards have them.
+    pub diagonals: bool,
+    /// Every cell on the card.
+    pub blackout: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            lines: true,
+            diagonals: false,
+            blackout: false,
+        }
+    }
+}
+
+impl Rules {
+    /// Parses a comma-separated list of `lines`, `diagonals` and `blackout`.
+    pub fn parse(list: &str) -> Result<Rules, String> {
+        let mut rules = Rules {
+            lines: false,
+            diagonals: false,
+            blackout: false,
+        };
+        for name in list.split(',') {
+            match name.trim() {
+                "lines" => rules.lines = true,
+                "diagonals" => rules.diagonals = true,
+                "blackout" => rules.blackout = true,
+                other => return Err(format!("Unknown rule: {}", other)),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    numbers: Vec<Vec<i32>>,
+    called: Vec<Vec<bool>>,
+    /// Where each number appears on the board.
+    cells: HashMap<i32, Vec<(usize, usize)>>,
+    row_hits: Vec<usize>,
+    col_hits: Vec<usize>,
+    /// Hits on the main and anti-diagonal.
+    diagonal_hits: [usize; 2],
+    marked: usize,
+    unmarked_sum: i64,
+    rules: Rules,
+    won: bool,
+    just_called: i32,
+}
+
+impl Board {
+    /// Parses one board from `section`, a slice of the whole `input`.
+    fn parse(input: &str, section: &str) -> Result<Board, ParseError> {
+        let mut lines: Vec<Vec<i32>> = Vec::new();
+        for buf in aoc_common::lines(section) {
+            let mut line: Vec<i32> = Vec::new();
+            for part in buf.split_whitespace() {
+                let val: i32 = aoc_common::parse_token(input, part, "Invalid integer")?;
+                line.push(val);
+            }
+            if !lines.is_empty() && line.len() != lines[0].len() {
+                return Err(ParseError::at(
+                    input,
+                    buf,
+                    "Board row length differs from the first row",
+                ));
+            }
+            lines.push(line);
+        }
+        Ok(Board::new(lines))
+    }
+
+    fn new(numbers: Vec<Vec<i32>>) -> Board {
+        let mut cells: HashMap<i32, Vec<(usize, usize)>> = HashMap::new();
+        for (i, row) in numbers.iter().enumerate() {
+            for (j, number) in row.iter().enumerate() {
+                cells.entry(*number).or_default().push((i, j));
+            }
+        }
+        let mut board = Board {
+            called: numbers.iter().map(|row| vec![false; row.len()]).collect(),
+            row_hits: vec![0; numbers.len()],
+            col_hits: vec![0; numbers[0].len()],
+            numbers,
+            cells,
+            diagonal_hits: [0, 0],
+            marked: 0,
+            unmarked_sum: 0,
+            rules: Rules::default(),
+            won: false,
+            just_called: 0,
+        };
+        board.clear();
+        board
+    }
+
+    /// Marks `number` wherever it appears, in time proportional to its occurrences.
+    pub fn call(&mut self, number: i32) {
+        let Some(count) = self.cells.get(&number).map(|cells| cells.len()) else {
+            return;
+        };
+        for k in 0..count {
+            let (i, j) = self.cells[&number][k];
+            self.mark(i, j);
+        }
+    }
+
+    /// Marks the cell at `(row, col)` as called.
+    fn mark(&mut self, row: usize, col: usize) {
+        if self.called[row][col] {
+            return;
+        }
+        self.called[row][col] = true;
+        self.just_called = self.numbers[row][col];
+        self.unmarked_sum -= self.just_called as i64;
+        self.row_hits[row] += 1;
+        self.col_hits[col] += 1;
+        self.marked += 1;
+        let (height, width) = (self.numbers.len(), self.numbers[0].len());
+        let square = height == width;
+        if square && row == col {
+            self.diagonal_hits[0] += 1;
+        }
+        if square && row + col == width - 1 {
+            self.diagonal_hits[1] += 1;
+        }
+
+        let rules = self.rules;
+        if (rules.lines && (self.row_hits[row] == width || self.col_hits[col] == height))
+            || (rules.diagonals && square && self.diagonal_hits.contains(&width))
+            || (rules.blackout && self.marked == width * height)
+        {
+            self.won = true;
+        }
+    }
+
+    /// Switches the winning patterns; takes effect from the next call.
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    pub fn clear(&mut self) {
+        for row in self.called.iter_mut() {
+            row.fill(false);
+        }
+        self.row_hits.fill(0);
+        self.col_hits.fill(0);
+        self.diagonal_hits = [0, 0];
+        self.marked = 0;
+        self.unmarked_sum = self.numbers.iter().flatten().map(|x| *x as i64).sum();
+        self.won = false;
+        self.just_called = 0;
+    }
+
+    pub fn score(&self) -> i64 {
+        self.unmarked_sum * self.just_called as i64
+    }
+
+    pub fn is_winner(&self) -> bool {
+        self.won
+    }
+}
+
+/// Every board that completed on one call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    /// Index into the calls of the number that completed the boards.
+    pub turn: usize,
+    /// `(board, score)` for each winner, in input order.
+    pub winners: Vec<(usize, i64)>,
+}
+
+impl Round {
+    /// The winning score, or every winner's score when the call was a tie.
+    pub fn describe(&self) -> String {
+        match self.winners[..] {
+            [(_, score)] => score.to_string(),
+            _ => {
+                let scores: Vec<String> = self
+                    .winners
+                    .iter()
+                    .map(|(board, score)| format!("board {} scores {}", board + 1, score))
+                    .collect();
+                format!("tie: {}", scores.join(", "))
+            }
+        }
+    }
+}
+
+/// Replays `calls` under `rules` and returns the boards that win, grouped by call.
+///
+/// Each call only touches the cells holding that number, found through a
+/// shared index from number to `(board, row, col)`.
+pub fn win_order(calls: &[i32], boards: &[Board], rules: Rules) -> Vec<Round> {
+    let mut boards = boards.to_vec();
+    let mut index: HashMap<i32, Vec<(usize, usize, usize)>> = HashMap::new();
+    for (b, board) in boards.iter_mut().enumerate() {
+        board.clear();
+        board.set_rules(rules);
+        for (number, cells) in board.cells.iter() {
+            let entry = index.entry(*number).or_default();
+            entry.extend(cells.iter().map(|(i, j)| (b, *i, *j)));
+        }
+    }
+    for entries in index.values_mut() {
+        entries.sort();
+    }
+
+    let mut rounds: Vec<Round> = Vec::new();
+    let mut won: usize = 0;
+    for (turn, call) in calls.iter().enumerate() {
+        let Some(entries) = index.get(call) else {
+            continue;
+        };
+        let mut winners: Vec<(usize, i64)> = Vec::new();
+        // Mark every occurrence on a board before scoring it.
+        for cells in entries.chunk_by(|a, b| a.0 == b.0) {
+            let board = &mut boards[cells[0].0];
+            if board.is_winner() {
+                continue;
+            }
+            for &(_, i, j) in cells {
+                board.mark(i, j);
+            }
+            if board.is_winner() {
+                winners.push((cells[0].0, board.score()));
+            }
+        }
+        if !winners.is_empty() {
+            won += winners.len();
+            rounds.push(Round { turn, winners });
+        }
+        if won == boards.len() {
+            break;
+        }
+    }
+    rounds
+}
+
+fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    let sections = aoc_common::sections(input);
+    let calls: Vec<i32> = match sections.first() {
+        Some(section) => section
+            .split(',')
+            .map(|val| aoc_common::parse_token(input, val.trim(), "Invalid integer"))
+            .collect::<Result<Vec<i32>, ParseError>>()?,
+        None => return Err(ParseError::at_end(input, "Missing called numbers")),
+    };
+    if sections.len() < 2 {
+        return Err(ParseError::at_end(input, "Missing boards"));
+    }
+    let boards: Vec<Board> = sections
+        .iter()
+        .skip(1)
+        .map(|section| Board::parse(input, section))
+        .collect::<Result<Vec<Board>, ParseError>>()?;
+    Ok((calls, boards))
+}
+
+/// How one board fared over many shuffled games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Odds {
+    /// Share of games this board won; a tie splits the game between its winners.
+    pub win_probability: f64,
+    /// Mean number of draws until this board completes, over games where it did.
+    pub expected_turn: Option<f64>,
+    /// Mean score on the draws where this board won.
+    pub mean_winning_score: Option<f64>,
+}
+
+/// Plays `trials` games, each drawing every number in `0..numbers` in a
+/// shuffled order from a generator seeded with `seed`, and tallies each board.
+pub fn simulate(
+    boards: &[Board],
+    numbers: i32,
+    trials: usize,
+    seed: u64,
+    rules: Rules,
+) -> Vec<Odds> {
+    let mut rng = Rng::new(seed);
+    let mut boards = boards.to_vec();
+    for board in boards.iter_mut() {
+        board.set_rules(rules);
+    }
+    let mut draws: Vec<i32> = (0..numbers).collect();
+    let mut wins: Vec<f64> = vec![0.0; boards.len()];
+    let mut win_scores: Vec<(usize, i64)> = vec![(0, 0); boards.len()];
+    let mut turns: Vec<(usize, usize)> = vec![(0, 0); boards.len()];
+
+    for _ in 0..trials {
+        rng.shuffle(&mut draws);
+        for board in boards.iter_mut() {
+            board.clear();
+        }
+        let mut done: usize = 0;
+        let mut decided = false;
+        for (turn, number) in draws.iter().enumerate() {
+            let mut winners: Vec<usize> = Vec::new();
+            for (b, board) in boards.iter_mut().enumerate() {
+                if board.is_winner() {
+                    continue;
+                }
+                board.call(*number);
+                if board.is_winner() {
+                    winners.push(b);
+                    turns[b].0 += 1;
+                    turns[b].1 += turn + 1;
+                }
+            }
+            if !decided && !winners.is_empty() {
+                decided = true;
+                for b in winners.iter() {
+                    wins[*b] += 1.0 / winners.len() as f64;
+                    win_scores[*b].0 += 1;
+                    win_scores[*b].1 += boards[*b].score();
+                }
+            }
+            done += winners.len();
+            if done == boards.len() {
+                break;
+            }
+        }
+    }
+
+    let mean = |(count, total): (usize, i64)| match count {
+        0 => None,
+        _ => Some(total as f64 / count as f64),
+    };
+    (0..boards.len())
+        .map(|b| Odds {
+            win_probability: wins[b] / trials.max(1) as f64,
+            expected_turn: mean((turns[b].0, turns[b].1 as i64)),
+            mean_winning_score: mean(win_scores[b]),
+        })
+        .collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = (Vec<i32>, Vec<Board>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (calls, boards) = input;
+        let rounds = win_order(calls, boards, Rules::default());
+        match rounds.first() {
+            Some(round) => Ok(round.describe()),
+            None => Err(SolveError::new("No board wins")),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let (calls, boards) = input;
+        let rounds = win_order(calls, boards, Rules::default());
+        let won: usize = rounds.iter().map(|round| round.winners.len()).sum();
+        if won < boards.len() {
+            return Some(Err(SolveError::new(format!(
+                "{} of {} boards never win",
+                boards.len() - won,
+                boards.len()
+            ))));
+        }
+        Some(Ok(rounds.last().expect("Every board won").describe()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_every_board() {
+        let (calls, boards) = parse_input(include_str!("../example.txt")).unwrap();
+        let rounds = win_order(&calls, &boards, Rules::default());
+        let order: Vec<(usize, Vec<(usize, i64)>)> = rounds
+            .into_iter()
+            .map(|round| (round.turn, round.winners))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (11, vec![(2, 4512)]),
+                (13, vec![(0, 2192)]),
+                (14, vec![(1, 1924)])
+            ]
+        );
+    }
+
+    #[test]
+    fn call_and_clear() {
+        let mut board = Board::new(vec![vec![1, 2], vec![3, 4]]);
+        board.call(1);
+        board.call(1);
+        assert!(!board.is_winner());
+        board.call(3);
+        assert!(board.is_winner());
+        assert_eq!(board.score(), 6 * 3);
+        board.clear();
+        assert!(!board.is_winner());
+        assert_eq!(board.score(), 0);
+    }
+
+    #[test]
+    fn repeated_number_marks_every_cell() {
+        let boards = vec![Board::new(vec![vec![1, 2], vec![3, 1]])];
+        let rounds = win_order(&[2, 1], &boards, Rules::default());
+        assert_eq!(rounds[0].winners, vec![(0, 3)]);
+    }
+
+    #[test]
+    fn reports_boards_that_never_win() {
+        let input = parse_input("7,4\n\n1 2\n3 4\n").unwrap();
+        assert_eq!(
+            Day4::part1(&input).unwrap_err().to_string(),
+            "No board wins"
+        );
+        let input = parse_input("1,2\n\n1 2\n3 4\n\n5 6\n7 8\n").unwrap();
+        assert_eq!(Day4::part1(&input).unwrap(), "14");
+        assert_eq!(
+            Day4::part2(&input).unwrap().unwrap_err().to_string(),
+            "1 of 2 boards never win"
+        );
+    }
+
+    #[test]
+    fn configurable_rules() {
+        let board = Board::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let boards = vec![board];
+        let diagonal = [1, 5, 9];
+        assert!(win_order(&diagonal, &boards, Rules::default()).is_empty());
+        let rules = Rules::parse("diagonals").unwrap();
+        let rounds = win_order(&diagonal, &boards, rules);
+        assert_eq!(rounds[0].winners, vec![(0, 30 * 9)]);
+
+        let blackout = Rules::parse("blackout").unwrap();
+        let calls: Vec<i32> = (1..=9).collect();
+        assert_eq!(win_order(&calls[..8], &boards, blackout), vec![]);
+        assert_eq!(win_order(&calls, &boards, blackout)[0].turn, 8);
+        assert!(Rules::parse("corners").is_err());
+    }
+
+    #[test]
+    fn rectangular_boards_and_ties() {
+        let boards = vec![
+            Board::new(vec![vec![1, 2, 3], vec![4, 5, 6]]),
+            Board::new(vec![vec![3, 2, 1], vec![9, 9, 9]]),
+        ];
+        let rounds = win_order(&[1, 2, 3], &boards, Rules::default());
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].winners, vec![(0, 15 * 3), (1, 27 * 3)]);
+        assert_eq!(
+            rounds[0].describe(),
+            "tie: board 1 scores 45, board 2 scores 81"
+        );
+    }
+
+    #[test]
+    fn simulates_reproducibly() {
+        let (_, boards) = parse_input(include_str!("../example.txt")).unwrap();
+        let odds = simulate(&boards, 27, 200, 1, Rules::default());
+        assert_eq!(odds, simulate(&boards, 27, 200, 1, Rules::default()));
+        let total: f64 = odds.iter().map(|o| o.win_probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        for o in odds.iter() {
+            let turn = o.expected_turn.unwrap();
+            assert!((5.0..=27.0).contains(&turn));
+        }
+    }
+
+    #[test]
+    fn lone_board_always_wins() {
+        let boards = vec![Board::new(vec![vec![0, 1], vec![2, 3]])];
+        let odds = simulate(&boards, 4, 50, 9, Rules::default());
+        assert_eq!(odds[0].win_probability, 1.0);
+        let turn = odds[0].expected_turn.unwrap();
+        assert!((2.0..=3.0).contains(&turn));
+        assert!(odds[0].mean_winning_score.is_some());
+    }
+}
@@ -1,190 +1,72 @@
-use std::io;
+use aoc_common::runner::{self, ExtraArgs, Options};
+use aoc_common::Solution;
+use day4::{Day4, Rules};
 
-#[derive(Debug)]
-struct Board {
-    numbers: Vec<Vec<i32>>,
-    called: Vec<Vec<bool>>,
-    just_called: i32,
-}
-
-impl Board {
-    fn parse(stream: &mut io::Stdin) -> Option<Board> {
-        let mut buf = String::new();
-        let mut lines: Vec<Vec<i32>> = Vec::new();
-        loop {
-            let n = stream.read_line(&mut buf).expect("Failed to read stdin");
-            if n == 0 || buf.trim().len() == 0 {
-                break;
-            }
-            let mut line: Vec<i32> = Vec::new();
-            for part in buf.trim().split(" ") {
-                if part.trim().len() > 0 {
-                    let val: i32 = part.trim().parse().expect("Invalid integer");
-                    line.push(val);
-                }
-            }
-            lines.push(line);
-            buf.clear();
-        }
-        let called = lines
+/// Prints every winning call under `rules`, first to last.
+fn rank(rules: Rules, options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let (calls, boards) = Day4::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    for round in day4::win_order(&calls, &boards, rules) {
+        let won: Vec<String> = round
+            .winners
             .iter()
-            .map(|line| line.iter().map(|_| false).collect())
+            .map(|(board, _)| (board + 1).to_string())
             .collect();
-        if lines.len() == 0 {
-            return None;
-        }
-        Some(Board {
-            numbers: lines,
-            called,
-            just_called: 0,
-        })
-    }
-
-    fn call(&mut self, number: i32) {
-        for i in 0..self.numbers.len() {
-            for j in 0..self.numbers[i].len() {
-                if self.numbers[i][j] == number {
-                    self.called[i][j] = true;
-                    self.just_called = number
-                }
-            }
-        }
-    }
-
-    fn clear(&mut self) {
-        for i in 0..self.numbers.len() {
-            for j in 0..self.numbers[i].len() {
-                self.called[i][j] = false;
-            }
-        }
-    }
-
-    fn score(&self) -> i64 {
-        let mut score: i64 = 0;
-        for i in 0..self.numbers.len() {
-            for j in 0..self.numbers[i].len() {
-                if !self.called[i][j] {
-                    let num: i64 = self.numbers[i][j].into();
-                    score += num;
-                }
-            }
-        }
-        let num: i64 = self.just_called.into();
-        score * num
-    }
-
-    fn is_winner(&self) -> bool {
-        for i in 0..self.numbers.len() {
-            let mut row_wins = true;
-            for j in 0..self.numbers[i].len() {
-                if !self.called[i][j] {
-                    row_wins = false;
-                    break;
-                }
-            }
-            if row_wins {
-                // println!("Row {} in {:?} wins", i, self);
-                return true;
-            }
-        }
-
-        for j in 0..self.numbers[0].len() {
-            let mut col_wins = true;
-            for i in 0..self.numbers.len() {
-                if !self.called[i][j] {
-                    col_wins = false;
-                    break;
-                }
-            }
-            if col_wins {
-                // println!("Col {} in {:?} wins", j, self);
-                return true;
-            }
-        }
-
-        return false;
-    }
-}
-
-fn parse_input() -> (Vec<i32>, Vec<Board>) {
-    let mut buffer = String::new();
-    let mut state = 0;
-    let mut calls: Vec<i32> = Vec::new();
-    let mut boards: Vec<Board> = Vec::new();
-
-    loop {
-        if state == 2 {
-            match Board::parse(&mut io::stdin()) {
-                Some(board) => boards.push(board),
-                None => break,
-            }
-        } else {
-            let n = io::stdin()
-                .read_line(&mut buffer)
-                .expect("Failed to read stdin");
-            if n == 0 {
-                // End of input
-                break;
-            }
-            match state {
-                0 => {
-                    calls = buffer
-                        .trim()
-                        .split(",")
-                        .map(|x| {
-                            let val: i32 = x.parse().expect("Invalid integer");
-                            val
-                        })
-                        .collect();
-                    state = 1;
-                }
-                1 => {
-                    state = 2;
-                }
-                _ => panic!("Invalid state {}", state),
-            };
-            buffer.clear();
-        }
+        println!(
+            "Call {} ({}): board {} -> {}",
+            round.turn + 1,
+            calls[round.turn],
+            won.join(", "),
+            round.describe()
+        );
     }
-    (calls, boards)
+    Ok(())
 }
 
-fn part1(calls: &[i32], boards: &mut [Board]) -> i64 {
-    for &call in calls {
-        for board in boards.iter_mut() {
-            board.call(call);
-            if board.is_winner() {
-                return board.score();
-            }
-        }
+/// Prints each board's odds over `trials` shuffled draws of every number up to the largest called.
+fn simulate(rules: Rules, trials: usize, seed: u64, options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let (calls, boards) = Day4::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    let numbers = calls.iter().max().map_or(0, |x| x + 1);
+    let odds = day4::simulate(&boards, numbers, trials, seed, rules);
+    println!("board,win_probability,expected_turn,mean_winning_score");
+    let show = |x: Option<f64>| x.map_or("-".to_string(), |x| format!("{:.3}", x));
+    for (b, o) in odds.iter().enumerate() {
+        println!(
+            "{},{:.4},{},{}",
+            b + 1,
+            o.win_probability,
+            show(o.expected_turn),
+            show(o.mean_winning_score)
+        );
     }
-    panic!("No winner");
-}
-
-fn part2(calls: &[i32], boards: &mut [Board]) -> i64 {
-    let mut winners: usize = 0;
-    let n = boards.len();
-    for &call in calls {
-        for board in boards.iter_mut() {
-            if !board.is_winner() {
-                board.call(call);
-                if board.is_winner() {
-                    winners += 1;
-                    if winners == n {
-                        return board.score();
-                    }
-                }
-            }
-        }
-    }
-    panic!("Not all boards win");
+    Ok(())
 }
 
 fn main() {
-    let (calls, mut boards) = parse_input();
-    println!("Part 1: {}", part1(&calls[..], boards.as_mut_slice()));
-    for board in boards.iter_mut() {
-        board.clear();
+    // `--rules` ranks the boards under other rules; `--simulate` plays
+    // shuffled games instead of the input's calls.
+    let mut args = ExtraArgs::from_env::<Day4>(
+        "[--rules lines,diagonals,blackout] [--simulate TRIALS [--seed N]]",
+    );
+    let rules = args.take_parsed("--rules", Rules::parse);
+    let trials = args.take_parsed("--simulate", |x| match x.parse() {
+        Ok(trials) if trials > 0 => Ok(trials),
+        _ => Err(format!("Invalid trial count: {}", x)),
+    });
+    let seed = args.take_parsed("--seed", |x| {
+        x.parse::<u64>().map_err(|_| format!("Invalid seed: {}", x))
+    });
+    if seed.is_some() && trials.is_none() {
+        args.usage_error("--seed needs --simulate");
+    }
+    if rules.is_none() && trials.is_none() {
+        args.run_answers::<Day4>();
+        return;
     }
-    println!("Part 2: {}", part2(&calls[..], boards.as_mut_slice()));
+    let rules = rules.unwrap_or_default();
+    args.run_mode(|options| match trials {
+        Some(trials) => simulate(rules, trials, seed.unwrap_or(0), options),
+        None => rank(rules, options),
+    });
 }
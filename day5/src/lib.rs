@@ -0,0 +1,535 @@
+use aoc_common::{Answer, Grid, ParseError, Solution};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+fn parse_pair(input: &str, pair: &str) -> Result<(i32, i32), ParseError> {
+    let vec: Vec<i32> = pair
+        .split(',')
+        .map(|val| aoc_common::parse_token(input, val.trim(), "Invalid integer"))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+    if vec.len() == 2 {
+        Ok((vec[0], vec[1]))
+    } else {
+        Err(ParseError::at(input, pair, "Invalid pair"))
+    }
+}
+
+fn parse_segments(input: &str) -> Result<Vec<((i32, i32), (i32, i32))>, ParseError> {
+    let mut segments: Vec<((i32, i32), (i32, i32))> = Vec::new();
+    for line in aoc_common::lines(input) {
+        // 0,9 -> 5,9
+        let segment_vec: Vec<(i32, i32)> = line
+            .split(" -> ")
+            .map(|pair| parse_pair(input, pair))
+            .collect::<Result<Vec<(i32, i32)>, ParseError>>()?;
+
+        if segment_vec.len() == 2 {
+            let segment = (segment_vec[0], segment_vec[1]);
+            segments.push(segment);
+        } else {
+            return Err(ParseError::at(input, line, "Invalid segment"));
+        }
+    }
+    Ok(segments)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The segment's direction divided by the gcd of its components, and how
+/// many of those steps it spans.
+fn reduced_step(start: (i32, i32), end: (i32, i32)) -> ((i64, i64), i64) {
+    let dx = end.0 as i64 - start.0 as i64;
+    let dy = end.1 as i64 - start.1 as i64;
+    let steps = gcd(dx.abs(), dy.abs());
+    if steps == 0 {
+        ((0, 0), 0)
+    } else {
+        ((dx / steps, dy / steps), steps)
+    }
+}
+
+/// Every integer point on the segment, stepping by its direction reduced by the gcd.
+fn lattice_points(start: (i32, i32), end: (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+    let (step, steps) = reduced_step(start, end);
+    (0..=steps).map(move |i| {
+        (
+            (start.0 as i64 + i * step.0) as i32,
+            (start.1 as i64 + i * step.1) as i32,
+        )
+    })
+}
+
+pub fn segments_to_coord_counts(
+    segments: &[((i32, i32), (i32, i32))],
+    only_unidirectional: bool,
+) -> HashMap<(i32, i32), usize> {
+    let mut counts: HashMap<(i32, i32), usize> = HashMap::new();
+    for &(start, end) in segments {
+        if only_unidirectional && !(start.0 == end.0 || start.1 == end.1) {
+            continue;
+        }
+        for point in lattice_points(start, end) {
+            *counts.entry(point).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+fn count_duplicates(counts: &HashMap<(i32, i32), usize>) -> usize {
+    let mut avoid: usize = 0;
+    for count in counts.values() {
+        if *count >= 2 {
+            avoid += 1;
+        }
+    }
+    avoid
+}
+
+/// Number of integer points covered by at least two of the inclusive `(from, to)` intervals.
+fn covered_twice(intervals: &[(i64, i64)]) -> u64 {
+    let mut events: Vec<(i64, i32)> = intervals
+        .iter()
+        .flat_map(|(from, to)| [(*from, 1), (to + 1, -1)])
+        .collect();
+    events.sort();
+    let mut depth = 0;
+    let mut covered: u64 = 0;
+    let mut prev: i64 = 0;
+    for (x, delta) in events {
+        if depth >= 2 {
+            covered += (x - prev) as u64;
+        }
+        depth += delta;
+        prev = x;
+    }
+    covered
+}
+
+/// A segment that is neither horizontal nor vertical, walked downwards one
+/// reduced step, and so one lattice row, at a time.
+#[derive(Debug, Clone, Copy)]
+struct Sloped {
+    /// The next lattice point still to be counted.
+    x: i64,
+    y: i64,
+    dx: i64,
+    /// Always positive.
+    dy: i64,
+    end_y: i64,
+}
+
+impl Sloped {
+    fn new(start: (i32, i32), end: (i32, i32)) -> Sloped {
+        let (start, end) = if start.1 <= end.1 {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        let ((dx, dy), _) = reduced_step(start, end);
+        Sloped {
+            x: start.0 as i64,
+            y: start.1 as i64,
+            dx,
+            dy,
+            end_y: end.1 as i64,
+        }
+    }
+
+    /// Moves on to the first lattice point at or below `row`.
+    fn skip_to(&mut self, row: i64) {
+        if self.y < row {
+            let steps = (row - self.y + self.dy - 1) / self.dy;
+            self.x += steps * self.dx;
+            self.y += steps * self.dy;
+        }
+    }
+
+    /// The first row from here on where this segment has a point in column `x`.
+    fn meets_column(&self, x: i64) -> Option<i64> {
+        let offset = x - self.x;
+        if self.dx == 0 || offset % self.dx != 0 || offset / self.dx < 0 {
+            return None;
+        }
+        Some(self.y + offset / self.dx * self.dy).filter(|row| *row <= self.end_y)
+    }
+
+    /// The first row from here on where both segments have the same point.
+    fn meets(&self, other: &Sloped) -> Option<i64> {
+        let det = other.dx * self.dy - self.dx * other.dy;
+        let (ox, oy) = ((other.x - self.x) as i128, (other.y - self.y) as i128);
+        let row = if det == 0 {
+            // Parallel: either collinear, sharing every lattice row, or apart.
+            if ox * self.dy as i128 != oy * self.dx as i128 {
+                return None;
+            }
+            self.y.max(other.y)
+        } else {
+            // Solve self + a * step == other + b * step for whole a, b >= 0.
+            let det = det as i128;
+            let a = other.dx as i128 * oy - other.dy as i128 * ox;
+            let b = self.dx as i128 * oy - self.dy as i128 * ox;
+            if a % det != 0 || b % det != 0 || a / det < 0 || b / det < 0 {
+                return None;
+            }
+            self.y + (a / det) as i64 * self.dy
+        };
+        Some(row).filter(|row| *row <= self.end_y.min(other.end_y))
+    }
+}
+
+/// Counts the points where segments overlap, row by row, without visiting
+/// every covered point.
+///
+/// Horizontal segments become one interval in their row. Vertical segments
+/// are kept as start/end events, and sloped segments are walked a reduced
+/// step at a time. Only rows where something starts or ends, or where a
+/// sloped segment meets another one, are visited; the rows between them,
+/// where only verticals can overlap, are counted once and multiplied.
+fn count_overlaps(segments: &[((i32, i32), (i32, i32))], only_unidirectional: bool) -> u64 {
+    let mut rows: BTreeMap<i64, Vec<(i64, i64)>> = BTreeMap::new();
+    let mut verticals: BTreeMap<i64, Vec<(i64, i32)>> = BTreeMap::new();
+    let mut starts: BTreeMap<i64, Vec<Sloped>> = BTreeMap::new();
+    for &(start, end) in segments {
+        if start.1 == end.1 {
+            let (from, to) = (start.0.min(end.0) as i64, start.0.max(end.0) as i64);
+            rows.entry(start.1 as i64).or_default().push((from, to));
+        } else if start.0 == end.0 {
+            let (from, to) = (start.1.min(end.1) as i64, start.1.max(end.1) as i64);
+            verticals.entry(from).or_default().push((start.0 as i64, 1));
+            verticals
+                .entry(to + 1)
+                .or_default()
+                .push((start.0 as i64, -1));
+        } else if !only_unidirectional {
+            let sloped = Sloped::new(start, end);
+            starts.entry(sloped.y).or_default().push(sloped);
+        }
+    }
+
+    let events: BTreeSet<i64> = rows
+        .keys()
+        .chain(verticals.keys())
+        .chain(starts.keys())
+        .copied()
+        .collect();
+    // Column -> number of vertical segments covering the current row.
+    let mut active: BTreeMap<i64, i32> = BTreeMap::new();
+    let mut moving: Vec<Sloped> = Vec::new();
+    let mut total: u64 = 0;
+    let mut next_row = events.first().copied();
+    while let Some(y) = next_row {
+        for &(x, delta) in verticals.get(&y).into_iter().flatten() {
+            let count = active.entry(x).or_insert(0);
+            *count += delta;
+            if *count == 0 {
+                active.remove(&x);
+            }
+        }
+        moving.extend(starts.get(&y).into_iter().flatten());
+        // Rows skipped since the last visit held no overlaps for these.
+        for sloped in moving.iter_mut() {
+            sloped.skip_to(y);
+        }
+
+        let mut intervals: Vec<(i64, i64)> = rows.get(&y).cloned().unwrap_or_default();
+        for (x, count) in active.iter() {
+            for _ in 0..*count {
+                intervals.push((*x, *x));
+            }
+        }
+        for sloped in moving.iter_mut().filter(|sloped| sloped.y == y) {
+            intervals.push((sloped.x, sloped.x));
+            sloped.skip_to(y + 1);
+        }
+        moving.retain(|sloped| sloped.y <= sloped.end_y);
+        total += covered_twice(&intervals);
+
+        // Visit the next event, or sooner if a sloped segment meets a
+        // vertical or another sloped segment first.
+        let mut next = events.range(y + 1..).next().copied();
+        let mut consider = |row: Option<i64>| {
+            if let Some(row) = row {
+                next = Some(next.map_or(row, |next| next.min(row)));
+            }
+        };
+        for (i, sloped) in moving.iter().enumerate() {
+            for x in active.keys() {
+                consider(sloped.meets_column(*x));
+            }
+            for other in moving[i + 1..].iter() {
+                consider(sloped.meets(other));
+            }
+        }
+        if let Some(next) = next {
+            let stacked = active.values().filter(|count| **count >= 2).count() as u64;
+            total += stacked * (next - y - 1) as u64;
+        }
+        next_row = next;
+    }
+    total
+}
+
+/// The most cells [`coverage_grid`] will lay out.
+pub const MAX_MAP_CELLS: u64 = 1 << 24;
+
+/// The coverage map's bounding box holds more than [`MAX_MAP_CELLS`] cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapTooLarge {
+    pub width: u64,
+    pub height: u64,
+}
+
+impl fmt::Display for MapTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a {}x{} map is too large to lay out (at most {} cells)",
+            self.width, self.height, MAX_MAP_CELLS
+        )
+    }
+}
+
+impl Error for MapTooLarge {}
+
+/// Counts how many segments, diagonals included, cover each cell of a grid
+/// spanning every segment and the origin. Returns the `(x, y)` of its
+/// top-left cell and the grid, checking the size before drawing anything.
+pub fn coverage_grid(
+    segments: &[((i32, i32), (i32, i32))],
+) -> Result<((i32, i32), Grid<usize>), MapTooLarge> {
+    let xs = segments
+        .iter()
+        .flat_map(|(start, end)| [start.0, end.0])
+        .chain([0]);
+    let ys = segments
+        .iter()
+        .flat_map(|(start, end)| [start.1, end.1])
+        .chain([0]);
+    let (min_x, max_x) = (xs.clone().min().unwrap(), xs.max().unwrap());
+    let (min_y, max_y) = (ys.clone().min().unwrap(), ys.max().unwrap());
+    let width = (max_x as i64 - min_x as i64 + 1) as u64;
+    let height = (max_y as i64 - min_y as i64 + 1) as u64;
+    if width * height > MAX_MAP_CELLS {
+        return Err(MapTooLarge { width, height });
+    }
+    let mut grid = Grid::from_fn(height as usize, width as usize, |_| 0);
+    for &(start, end) in segments {
+        for (x, y) in lattice_points(start, end) {
+            grid[((y - min_y) as usize, (x - min_x) as usize)] += 1;
+        }
+    }
+    Ok(((min_x, min_y), grid))
+}
+
+/// Draws the coverage map like the puzzle does: `.` for no line, otherwise
+/// the count, with `+` standing in for counts above 9.
+pub fn render(grid: &Grid<usize>) -> String {
+    grid.render(|count| match count {
+        0 => '.',
+        1..=9 => char::from_digit(*count as u32, 10).unwrap(),
+        _ => '+',
+    })
+}
+
+/// The coverage map as a plain (ASCII) PGM image, brighter where more lines cross.
+pub fn pgm(grid: &Grid<usize>) -> String {
+    let max = grid.iter().copied().max().unwrap_or(0).max(1);
+    let mut out = format!("P2\n{} {}\n{}\n", grid.width(), grid.height(), max);
+    for row in 0..grid.height() {
+        let values: Vec<String> = (0..grid.width())
+            .map(|col| grid[(row, col)].to_string())
+            .collect();
+        out.push_str(&values.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// Whether `point` is one of the lattice points of `segment`.
+fn crosses(segment: ((i32, i32), (i32, i32)), point: (i32, i32)) -> bool {
+    let (start, end) = segment;
+    let ((sx, sy), steps) = reduced_step(start, end);
+    if steps == 0 {
+        return point == start;
+    }
+    let px = point.0 as i64 - start.0 as i64;
+    let py = point.1 as i64 - start.1 as i64;
+    // The point must be a whole number of reduced steps along the segment.
+    let k = if sx != 0 { px / sx } else { py / sy };
+    (0..=steps).contains(&k) && (k * sx, k * sy) == (px, py)
+}
+
+/// A heavily overlapped point and the segments through it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hotspot {
+    pub point: (i32, i32),
+    pub count: usize,
+    /// Indices into the input segments, in input order.
+    pub segments: Vec<usize>,
+}
+
+/// The `n` most-overlapped points of a [`coverage_grid`] whose top-left cell
+/// is at `origin`, most first, ties broken by `(y, x)`.
+pub fn hotspots(
+    segments: &[((i32, i32), (i32, i32))],
+    origin: (i32, i32),
+    grid: &Grid<usize>,
+    n: usize,
+) -> Vec<Hotspot> {
+    let mut points: Vec<((i32, i32), usize)> = grid
+        .positions()
+        .filter(|pos| grid[*pos] > 0)
+        .map(|(row, col)| {
+            let point = (origin.0 + col as i32, origin.1 + row as i32);
+            (point, grid[(row, col)])
+        })
+        .collect();
+    points.sort_by_key(|(point, count)| (std::cmp::Reverse(*count), point.1, point.0));
+    points
+        .into_iter()
+        .take(n)
+        .map(|(point, count)| Hotspot {
+            point,
+            count,
+            segments: (0..segments.len())
+                .filter(|i| crosses(segments[*i], point))
+                .collect(),
+        })
+        .collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Vec<((i32, i32), (i32, i32))>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_segments(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(count_overlaps(input, true).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(count_overlaps(input, false).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::rng::Rng;
+
+    #[test]
+    fn rasterizes_any_slope() {
+        let points: Vec<(i32, i32)> = lattice_points((0, 0), (4, 2)).collect();
+        assert_eq!(points, vec![(0, 0), (2, 1), (4, 2)]);
+        let points: Vec<(i32, i32)> = lattice_points((3, 7), (0, 5)).collect();
+        assert_eq!(points, vec![(3, 7), (0, 5)]);
+        assert_eq!(lattice_points((1, 1), (1, 1)).count(), 1);
+    }
+
+    #[test]
+    fn sweep_matches_point_counts() {
+        let segments = parse_segments(include_str!("../example.txt")).unwrap();
+        let mut rng = Rng::new(5);
+        let mut random = |n| rng.below(n) as i32;
+        let mut shapes: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        for _ in 0..200 {
+            shapes.push(((random(20), random(20)), (random(20), random(20))));
+        }
+        // Sparser, longer segments leave rows for the sweep to skip.
+        let mut sparse: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        for _ in 0..60 {
+            sparse.push(((random(90), random(90)), (random(90), random(90))));
+        }
+        for segments in [segments, shapes, sparse] {
+            for only_unidirectional in [true, false] {
+                let counts = segments_to_coord_counts(&segments, only_unidirectional);
+                assert_eq!(
+                    count_overlaps(&segments, only_unidirectional),
+                    count_duplicates(&counts) as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn huge_ranges() {
+        let segments = vec![
+            ((5, 0), (5, 1_000_000_000)),
+            ((5, 500_000_000), (5, 2_000_000_000)),
+            ((-1_000_000_000, 7), (1_000_000_000, 7)),
+        ];
+        // The stacked verticals, plus where the horizontal crosses the first one.
+        assert_eq!(count_overlaps(&segments, true), 500_000_001 + 1);
+
+        let segments = vec![
+            ((0, 0), (1_000_000_000, 1_000_000_000)),
+            ((0, 1_000_000_000), (1_000_000_000, 0)),
+            ((7, 0), (7, 1_000_000_000)),
+            ((0, 5), (1_000_000_000, 5)),
+            ((20, 20), (10, 10)),
+            (
+                (-2_000_000_000, -1_000_000_000),
+                (2_000_000_000, 1_000_000_000),
+            ),
+        ];
+        // Three crossings for the horizontal, two more for the vertical, the
+        // centre, 11 points shared with the short diagonal, and where the
+        // shallow line meets the diagonal and the horizontal.
+        assert_eq!(count_overlaps(&segments, false), 3 + 2 + 1 + 11 + 2);
+        assert!(crosses(segments[5], (2_000_000_000, 1_000_000_000)));
+    }
+
+    #[test]
+    fn renders_example() {
+        let segments = parse_segments(include_str!("../example.txt")).unwrap();
+        let (origin, grid) = coverage_grid(&segments).unwrap();
+        assert_eq!(origin, (0, 0));
+        let map = render(&grid);
+        assert_eq!(
+            map.lines().take(3).collect::<Vec<&str>>(),
+            vec!["1.1....11.", ".111...2..", "..2.1.111."]
+        );
+        let image = pgm(&grid);
+        assert!(image.starts_with("P2\n10 10\n3\n1 0 1 0 0 0 0 1 1 0\n"));
+
+        let far = [((-5, -5), (-5, -4)), ((9000, 9000), (9000, 9000))];
+        assert_eq!(
+            coverage_grid(&far).unwrap_err(),
+            MapTooLarge {
+                width: 9006,
+                height: 9006
+            }
+        );
+    }
+
+    #[test]
+    fn reports_hotspots() {
+        let segments = parse_segments(include_str!("../example.txt")).unwrap();
+        let (origin, grid) = coverage_grid(&segments).unwrap();
+        let top = hotspots(&segments, origin, &grid, 1);
+        assert_eq!(
+            top,
+            vec![Hotspot {
+                point: (4, 4),
+                count: 3,
+                segments: vec![1, 2, 8],
+            }]
+        );
+        assert!(crosses(((0, 0), (4, 2)), (2, 1)));
+        assert!(!crosses(((0, 0), (4, 2)), (1, 0)));
+        assert!(!crosses(((0, 0), (4, 2)), (6, 3)));
+    }
+}
@@ -0,0 +1,315 @@
+use aoc_common::{Answer, ParseError, Solution};
+
+/// How one species of fish reproduces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpeciesParams {
+    /// The timer a parent restarts at after giving birth.
+    pub reset_timer: usize,
+    /// The timer a newborn starts at.
+    pub newborn_timer: usize,
+}
+
+impl SpeciesParams {
+    /// The puzzle's species: parents reset to 6, newborns start at 8.
+    pub const LANTERNFISH: SpeciesParams = SpeciesParams {
+        reset_timer: 6,
+        newborn_timer: 8,
+    };
+
+    /// How many timer values a fish of this species can have.
+    pub fn states(&self) -> usize {
+        self.reset_timer.max(self.newborn_timer) + 1
+    }
+}
+
+/// Fish counts by timer, one vector per species.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Population {
+    species: Vec<(SpeciesParams, Vec<u64>)>,
+}
+
+impl Population {
+    pub fn new() -> Population {
+        Population::default()
+    }
+
+    /// Adds a species whose `counts[t]` fish have timer `t`.
+    pub fn add(&mut self, params: SpeciesParams, counts: Vec<u64>) {
+        assert_eq!(
+            counts.len(),
+            params.states(),
+            "Counts must cover every timer of the species"
+        );
+        self.species.push((params, counts));
+    }
+
+    pub fn species(&self) -> &[(SpeciesParams, Vec<u64>)] {
+        &self.species
+    }
+
+    /// Total fish of every species after `days`; see [`count_after`].
+    pub fn count_after<A: Arithmetic>(&self, arith: &A, days: u64) -> Option<A::Value> {
+        self.species
+            .iter()
+            .try_fold(arith.lift(0), |sum, (params, counts)| {
+                arith.add(sum, count_after(arith, params, counts, days)?)
+            })
+    }
+}
+
+/// Counts the fish in `input` by timer, rejecting timers `params` doesn't have.
+pub fn parse_counts(input: &str, params: &SpeciesParams) -> Result<Vec<u64>, ParseError> {
+    let mut counts: Vec<u64> = vec![0; params.states()];
+    for val in aoc_common::lines(input).flat_map(|line| line.split(',')) {
+        let val = val.trim();
+        let timer: usize = aoc_common::parse_token(input, val, "Invalid timer")?;
+        if timer >= counts.len() {
+            let message = format!("Timer must be between 0 and {}", counts.len() - 1);
+            return Err(ParseError::at(input, val, &message));
+        }
+        counts[timer] += 1;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+fn simulate_days(params: &SpeciesParams, starter_counts: &[u64], days: usize) -> Vec<u64> {
+    let mut counts: Vec<u64> = starter_counts.to_vec();
+    for _ in 0..days {
+        let parents = counts[0];
+        counts.rotate_left(1);
+        counts[params.states() - 1] = 0;
+        counts[params.reset_timer] += parents;
+        counts[params.newborn_timer] += parents;
+    }
+    counts
+}
+
+/// The number system fish counts are computed in.
+pub trait Arithmetic {
+    type Value: Copy;
+    fn lift(&self, x: u64) -> Self::Value;
+    /// `None` when the result doesn't fit.
+    fn add(&self, a: Self::Value, b: Self::Value) -> Option<Self::Value>;
+    fn mul(&self, a: Self::Value, b: Self::Value) -> Option<Self::Value>;
+}
+
+/// Exact counts, failing once they pass `u128::MAX` (after roughly 1000 days).
+pub struct Exact;
+
+impl Arithmetic for Exact {
+    type Value = u128;
+
+    fn lift(&self, x: u64) -> u128 {
+        x as u128
+    }
+
+    fn add(&self, a: u128, b: u128) -> Option<u128> {
+        a.checked_add(b)
+    }
+
+    fn mul(&self, a: u128, b: u128) -> Option<u128> {
+        a.checked_mul(b)
+    }
+}
+
+/// Counts modulo a (typically prime) modulus; never overflows.
+pub struct Modulo(pub u64);
+
+impl Arithmetic for Modulo {
+    type Value = u64;
+
+    fn lift(&self, x: u64) -> u64 {
+        x % self.0
+    }
+
+    fn add(&self, a: u64, b: u64) -> Option<u64> {
+        Some(((a as u128 + b as u128) % self.0 as u128) as u64)
+    }
+
+    fn mul(&self, a: u64, b: u64) -> Option<u64> {
+        Some(((a as u128 * b as u128) % self.0 as u128) as u64)
+    }
+}
+
+type Matrix<T> = Vec<Vec<T>>;
+
+/// `matrix[i][j]` is how many fish with timer `i` one fish with timer `j` becomes the next day.
+fn transition_matrix(params: &SpeciesParams) -> Matrix<u64> {
+    let n = params.states();
+    let mut matrix: Matrix<u64> = vec![vec![0; n]; n];
+    for timer in 1..n {
+        matrix[timer - 1][timer] = 1;
+    }
+    matrix[params.reset_timer][0] += 1;
+    matrix[params.newborn_timer][0] += 1;
+    matrix
+}
+
+fn mat_mul<A: Arithmetic>(
+    arith: &A,
+    lhs: &Matrix<A::Value>,
+    rhs: &Matrix<A::Value>,
+) -> Option<Matrix<A::Value>> {
+    let n = lhs.len();
+    let mut out: Matrix<A::Value> = vec![vec![arith.lift(0); n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            let mut sum = arith.lift(0);
+            for k in 0..n {
+                sum = arith.add(sum, arith.mul(lhs[i][k], rhs[k][j])?)?;
+            }
+            out[i][j] = sum;
+        }
+    }
+    Some(out)
+}
+
+fn mat_vec<A: Arithmetic>(
+    arith: &A,
+    matrix: &Matrix<A::Value>,
+    vector: &[A::Value],
+) -> Option<Vec<A::Value>> {
+    let mut out: Vec<A::Value> = Vec::with_capacity(vector.len());
+    for row in matrix {
+        let mut sum = arith.lift(0);
+        for (a, b) in row.iter().zip(vector) {
+            sum = arith.add(sum, arith.mul(*a, *b)?)?;
+        }
+        out.push(sum);
+    }
+    Some(out)
+}
+
+/// Total fish of one species after `days`, applying the transition matrix raised to `days`
+/// by repeated squaring, so the cost grows with `log(days)`.
+///
+/// Returns `None` if `arith` can't represent the result.
+pub fn count_after<A: Arithmetic>(
+    arith: &A,
+    params: &SpeciesParams,
+    starter_counts: &[u64],
+    days: u64,
+) -> Option<A::Value> {
+    let mut power: Matrix<A::Value> = transition_matrix(params)
+        .iter()
+        .map(|row| row.iter().map(|x| arith.lift(*x)).collect())
+        .collect();
+    let mut counts: Vec<A::Value> = starter_counts.iter().map(|x| arith.lift(*x)).collect();
+    let mut remaining = days;
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            counts = mat_vec(arith, &power, &counts)?;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            power = mat_mul(arith, &power, &power)?;
+        }
+    }
+    counts
+        .into_iter()
+        .try_fold(arith.lift(0), |sum, x| arith.add(sum, x))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Population;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let params = SpeciesParams::LANTERNFISH;
+        let counts = parse_counts(input, &params)?;
+        let mut population = Population::new();
+        population.add(params, counts);
+        Ok(population)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(input
+            .count_after(&Exact, 80)
+            .expect("80 days fit in a u128")
+            .to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let count = input
+            .count_after(&Exact, 256)
+            .expect("256 days fit in a u128");
+        Some(Ok(count.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn matches_daily_simulation() {
+        let params = SpeciesParams::LANTERNFISH;
+        let counts = parse_counts(EXAMPLE, &params).unwrap();
+        for days in [0, 1, 18, 80, 256, 300] {
+            let expected: u64 = simulate_days(&params, &counts, days).iter().sum();
+            assert_eq!(
+                count_after(&Exact, &params, &counts, days as u64),
+                Some(expected as u128)
+            );
+            let p = 1_000_000_007;
+            assert_eq!(
+                count_after(&Modulo(p), &params, &counts, days as u64),
+                Some(expected % p)
+            );
+        }
+    }
+
+    #[test]
+    fn huge_day_counts() {
+        let population = Day6::parse(EXAMPLE).unwrap();
+        assert!(population.count_after(&Exact, 900).is_some());
+        assert_eq!(population.count_after(&Exact, 2000), None);
+        let p = 998_244_353;
+        let result = population
+            .count_after(&Modulo(p), 1_000_000_000_000_000_000)
+            .unwrap();
+        assert!(result < p);
+        // The modular count is the exact one reduced mod p.
+        let exact = population.count_after(&Exact, 700).unwrap();
+        assert_eq!(
+            population.count_after(&Modulo(p), 700),
+            Some((exact % p as u128) as u64)
+        );
+    }
+
+    #[test]
+    fn mixed_species() {
+        // Newborns may start below the reset timer, and both may coincide.
+        let variants = [
+            SpeciesParams {
+                reset_timer: 2,
+                newborn_timer: 4,
+            },
+            SpeciesParams {
+                reset_timer: 5,
+                newborn_timer: 3,
+            },
+            SpeciesParams {
+                reset_timer: 1,
+                newborn_timer: 1,
+            },
+        ];
+        let mut population = Population::new();
+        let mut expected = 0;
+        for params in variants {
+            let counts = parse_counts("0,1,1", &params).unwrap();
+            assert_eq!(counts.len(), params.states());
+            expected += simulate_days(&params, &counts, 40).iter().sum::<u64>();
+            population.add(params, counts);
+        }
+        assert_eq!(population.species().len(), 3);
+        assert_eq!(population.count_after(&Exact, 40), Some(expected as u128));
+        let err = parse_counts("0,2", &variants[2]).unwrap_err();
+        assert!(err.to_string().contains("between 0 and 1"));
+    }
+}
@@ -1,62 +1,63 @@
-use std::io;
+use aoc_common::runner::{self, ExtraArgs, Options};
+use aoc_common::Solution;
+use day6::{Day6, Exact, Modulo, Population};
 
-fn parse_starter_fish() -> Vec<u8> {
-    let mut buffer = String::new();
-    let mut starter_fish: Vec<u8> = Vec::new();
-    loop {
-        let n = io::stdin()
-            .read_line(&mut buffer)
-            .expect("Failed to read stdin");
-        if n == 0 {
-            // End of input
-            break;
-        }
-        for val in buffer.trim().split(",") {
-            let x: u8 = val.parse().expect("Invalid integer");
-            starter_fish.push(x);
-        }
-        buffer.clear();
+/// Prints the starting timer counts of every species to stderr.
+fn dump(population: &Population) {
+    for (_, counts) in population.species() {
+        eprintln!("Starter counts: {:?}", counts);
     }
-    starter_fish
 }
 
-fn simulate_days(starter_counts: &[u64], days: usize) -> Vec<u64> {
-    // println!("Starter counts: {:?}", counts);
-    let mut counts: Vec<u64> = Vec::new();
-    counts.extend_from_slice(starter_counts);
-    for _ in 0..days {
-        counts = vec![
-            counts[1],
-            counts[2],
-            counts[3],
-            counts[4],
-            counts[5],
-            counts[6],
-            counts[7] + counts[0],
-            counts[8],
-            counts[0],
-        ];
-        // println!("After {} day(s), {} fish. Counts: {:?}", day + 1, counts.iter().sum::<u64>(), counts);
+/// Prints the population after `days`, exactly or modulo `modulus`.
+fn count(days: u64, modulus: Option<u64>, verbose: bool, options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let population = Day6::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    if verbose {
+        dump(&population);
+    }
+    match modulus {
+        Some(p) => match population.count_after(&Modulo(p), days) {
+            Some(total) => println!("Fish after {} days (mod {}): {}", days, p, total),
+            None => unreachable!("modular counts never overflow"),
+        },
+        None => match population.count_after(&Exact, days) {
+            Some(total) => println!("Fish after {} days: {}", days, total),
+            None => {
+                return Err(format!(
+                    "error: the count after {} days overflows a u128; pass --modulo P",
+                    days
+                ))
+            }
+        },
     }
-    return counts;
+    Ok(())
 }
 
 fn main() {
-    let starter_fish = parse_starter_fish();
-
-    let mut counts: Vec<u64> = vec![0; 9];
-    println!("Starters: {:?}", starter_fish);
-    for fish in starter_fish {
-        let offset: usize = fish.into();
-        counts[offset] += 1;
+    // `--days N` prints the population after any number of days; add
+    // `--modulo P` when the exact count would be too large. `--verbose` dumps
+    // the starting counts to stderr.
+    let mut args = ExtraArgs::from_env::<Day6>("[--days N [--modulo P]] [--verbose]");
+    let verbose = args.take_switch("--verbose");
+    let number = |flag: &'static str| {
+        move |x: &str| {
+            x.parse::<u64>()
+                .map_err(|_| format!("Invalid value for {}: {}", flag, x))
+        }
+    };
+    let days = args.take_parsed("--days", number("--days"));
+    let modulus = args.take_parsed("--modulo", number("--modulo"));
+    if modulus == Some(0) {
+        args.usage_error("--modulo must be positive");
+    }
+    match days {
+        Some(days) => args.run_mode(|options| count(days, modulus, verbose, options)),
+        None if modulus.is_some() => args.usage_error("--modulo needs --days"),
+        None => args.run_answers_with::<Day6>(|population| {
+            if verbose {
+                dump(population);
+            }
+        }),
     }
-    println!("Starter counts: {:?}", counts);
-    println!(
-        "Part 1: {}",
-        simulate_days(counts.as_slice(), 80).iter().sum::<u64>()
-    );
-    println!(
-        "Part 2: {}",
-        simulate_days(counts.as_slice(), 256).iter().sum::<u64>()
-    );
 }
@@ -0,0 +1,245 @@
+use aoc_common::{Answer, ParseError, Solution};
+
+fn parse_crabs(input: &str) -> Result<Vec<i64>, ParseError> {
+    let crabs: Vec<i64> = aoc_common::comma_separated(input)?;
+    if crabs.is_empty() {
+        return Err(ParseError::at_end(input, "No crabs"));
+    }
+    Ok(crabs)
+}
+
+/// How much fuel a crab spends moving `distance` steps.
+///
+/// Costs must be convex and never decrease with distance, which makes the
+/// total over all crabs convex in the target position.
+pub trait FuelCost {
+    fn cost(&self, distance: i64) -> i64;
+
+    /// Positions the optimum is known to lie between, given the sorted crabs.
+    /// `None` leaves the optimizer to search from the first crab to the last.
+    fn bounds(&self, _crabs: &[i64]) -> Option<(i64, i64)> {
+        None
+    }
+}
+
+/// One unit of fuel per step; the optimum is the median.
+pub struct Linear;
+
+impl FuelCost for Linear {
+    fn cost(&self, distance: i64) -> i64 {
+        distance
+    }
+
+    fn bounds(&self, crabs: &[i64]) -> Option<(i64, i64)> {
+        // With an even count, anywhere between the two middle crabs is optimal.
+        Some((crabs[(crabs.len() - 1) / 2], crabs[crabs.len() / 2]))
+    }
+}
+
+/// Each step costs one more than the last; the optimum is within half a step of the mean.
+pub struct Triangular;
+
+impl FuelCost for Triangular {
+    fn cost(&self, distance: i64) -> i64 {
+        distance * (distance + 1) / 2
+    }
+
+    fn bounds(&self, crabs: &[i64]) -> Option<(i64, i64)> {
+        // The real-valued optimum is within half a step of the mean, so the
+        // integer one is at most one step either side of that.
+        let mean = crabs.iter().sum::<i64>().div_euclid(crabs.len() as i64);
+        let (min, max) = (crabs[0], crabs[crabs.len() - 1]);
+        Some(((mean - 1).max(min), (mean + 2).min(max)))
+    }
+}
+
+/// Any other convex, non-decreasing cost, such as `Convex(|d| d * d)`.
+pub struct Convex<F: Fn(i64) -> i64>(pub F);
+
+impl<F: Fn(i64) -> i64> FuelCost for Convex<F> {
+    fn cost(&self, distance: i64) -> i64 {
+        (self.0)(distance)
+    }
+}
+
+/// Where the crabs line up and what it costs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alignment {
+    pub position: i64,
+    pub cost: i64,
+}
+
+/// Fuel for every crab to reach `position`.
+pub fn total_fuel(crabs: &[i64], model: &(impl FuelCost + ?Sized), position: i64) -> i64 {
+    crabs.iter().map(|x| model.cost((x - position).abs())).sum()
+}
+
+/// The cheapest position for the sorted, non-empty `crabs`, the leftmost on a tie.
+///
+/// Searches the model's bounds, or every position from the first crab to the
+/// last, by ternary search on the integers: since the total is convex, the
+/// optimum is where the cost stops falling.
+pub fn optimize(crabs: &[i64], model: &impl FuelCost) -> Alignment {
+    let (mut lo, mut hi) = model
+        .bounds(crabs)
+        .unwrap_or((crabs[0], crabs[crabs.len() - 1]));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if total_fuel(crabs, model, mid + 1) < total_fuel(crabs, model, mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    Alignment {
+        position: lo,
+        cost: total_fuel(crabs, model, lo),
+    }
+}
+
+/// Total fuel at every position from the first of the sorted crabs to the last.
+pub fn cost_curve(crabs: &[i64], model: &(impl FuelCost + ?Sized)) -> Vec<Alignment> {
+    (crabs[0]..=crabs[crabs.len() - 1])
+        .map(|position| Alignment {
+            position,
+            cost: total_fuel(crabs, model, position),
+        })
+        .collect()
+}
+
+/// The cheapest and second-cheapest points of a non-empty curve, leftmost first on ties.
+pub fn best_two(curve: &[Alignment]) -> (Alignment, Option<Alignment>) {
+    let rank = |alignment: &Alignment| (alignment.cost, alignment.position);
+    let mut best = curve[0];
+    let mut second: Option<Alignment> = None;
+    for point in curve[1..].iter() {
+        if rank(point) < rank(&best) {
+            second = Some(best);
+            best = *point;
+        } else if second.is_none_or(|second| rank(point) < rank(&second)) {
+            second = Some(*point);
+        }
+    }
+    (best, second)
+}
+
+/// One `position,<model>...` row per position, with a header naming each
+/// model, from curves built by [`cost_curve`] for the same crabs.
+pub fn curve_csv(curves: &[(&str, Vec<Alignment>)]) -> String {
+    let mut out = String::from("position");
+    for (name, _) in curves {
+        out.push(',');
+        out.push_str(name);
+    }
+    out.push('\n');
+    let positions = curves.first().map_or(&[][..], |(_, curve)| &curve[..]);
+    for (i, point) in positions.iter().enumerate() {
+        out.push_str(&point.position.to_string());
+        for (_, curve) in curves {
+            out.push_str(&format!(",{}", curve[i].cost));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut crabs = parse_crabs(input)?;
+        crabs.sort();
+        Ok(crabs)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(optimize(input, &Linear).cost.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(optimize(input, &Triangular).cost.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(crabs: &[i64], model: &impl FuelCost) -> Alignment {
+        best_two(&cost_curve(crabs, model)).0
+    }
+
+    #[test]
+    fn example_alignments() {
+        let crabs = Day7::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(
+            optimize(&crabs, &Linear),
+            Alignment {
+                position: 2,
+                cost: 37
+            }
+        );
+        assert_eq!(
+            optimize(&crabs, &Triangular),
+            Alignment {
+                position: 5,
+                cost: 168
+            }
+        );
+    }
+
+    #[test]
+    fn optimum_between_crabs() {
+        // No crab sits at 5, and both crab positions cost 55.
+        let crabs = vec![0, 10];
+        assert_eq!(optimize(&crabs, &Triangular).position, 5);
+        assert_eq!(optimize(&crabs, &Triangular).cost, 30);
+        assert_eq!(optimize(&crabs, &Linear), brute_force(&crabs, &Linear));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let square = Convex(|d| d * d);
+        let step = Convex(|d| (d - 3).max(0));
+        let cases: [&[i64]; 4] = [&[3], &[1, 1, 2, 9], &[-7, -2, 0, 0, 4, 30], &[5, 6, 50, 51]];
+        for crabs in cases {
+            assert_eq!(optimize(crabs, &Linear), brute_force(crabs, &Linear));
+            assert_eq!(
+                optimize(crabs, &Triangular),
+                brute_force(crabs, &Triangular)
+            );
+            assert_eq!(optimize(crabs, &square), brute_force(crabs, &square));
+            assert_eq!(optimize(crabs, &step), brute_force(crabs, &step));
+        }
+    }
+
+    #[test]
+    fn exports_curves() {
+        let crabs = Day7::parse(include_str!("../example.txt")).unwrap();
+        let csv = curve_csv(&[
+            ("linear", cost_curve(&crabs, &Linear)),
+            ("triangular", cost_curve(&crabs, &Triangular)),
+        ]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 18);
+        assert_eq!(lines[0], "position,linear,triangular");
+        assert_eq!(lines[3], "2,37,206");
+        assert_eq!(lines[6], "5,45,168");
+
+        let (best, second) = best_two(&cost_curve(&crabs, &Linear));
+        assert_eq!((best.position, best.cost), (2, 37));
+        assert_eq!(second.map(|x| (x.position, x.cost)), Some((3, 39)));
+        let (best, second) = best_two(&cost_curve(&crabs, &Triangular));
+        assert_eq!((best.position, second.unwrap().position), (5, 4));
+        assert_eq!(best_two(&cost_curve(&[4], &Linear)).1, None);
+        // Ties go left, and the runner-up may lie on either side.
+        let curve = cost_curve(&[0, 1, 2, 3], &Convex(|d| (d - 1).max(0)));
+        let (best, second) = best_two(&curve);
+        assert_eq!((best.position, second.unwrap().position), (1, 2));
+        let (best, second) = best_two(&cost_curve(&[0, 3, 3], &Linear));
+        assert_eq!((best.position, second.unwrap().position), (3, 2));
+    }
+}
@@ -1,54 +1,35 @@
-use std::io;
+use aoc_common::runner::{self, ExtraArgs, Options};
+use aoc_common::Solution;
+use day7::{Day7, Linear, Triangular};
 
-fn parse_crabs() -> Vec<i64> {
-    let mut buffer = String::new();
-    let mut crabs: Vec<i64> = Vec::new();
-    loop {
-        let n = io::stdin()
-            .read_line(&mut buffer)
-            .expect("Failed to read stdin");
-        if n == 0 {
-            // End of input
-            break;
+/// Prints the cost of every position as CSV, with each model's best two on stderr.
+fn curve(options: &Options) -> Result<(), String> {
+    let (source, raw) = runner::read_input(options)?;
+    let crabs = Day7::parse(&raw).map_err(|err| err.render(source, &raw))?;
+    let curves = [
+        ("linear", day7::cost_curve(&crabs, &Linear)),
+        ("triangular", day7::cost_curve(&crabs, &Triangular)),
+    ];
+    print!("{}", day7::curve_csv(&curves));
+    for (name, curve) in curves.iter() {
+        let (best, second) = day7::best_two(curve);
+        match second {
+            Some(second) => eprintln!(
+                "{}: best {} ({}), second {} ({})",
+                name, best.position, best.cost, second.position, second.cost
+            ),
+            None => eprintln!("{}: best {} ({})", name, best.position, best.cost),
         }
-        for val in buffer.trim().split(",") {
-            let x: i64 = val.parse().expect("Invalid integer");
-            crabs.push(x);
-        }
-        buffer.clear();
-    }
-    crabs
-}
-
-fn part1(crabs: &[i64]) -> i64 {
-    let median = crabs[crabs.len() / 2];
-    let cost = crabs.iter().map(|x| (x - median).abs()).sum::<i64>();
-    cost
-}
-
-fn part2(crabs: &[i64]) -> i64 {
-    let mut prev_cost: i64 = 0;
-    for i in 0..crabs.len() {
-        let trial = crabs[i];
-        let cost = crabs
-            .iter()
-            .map(|x| {
-                let diff = (x - trial).abs();
-                diff * (diff + 1) / 2
-            })
-            .sum::<i64>();
-        if i != 0 && cost > prev_cost {
-            break;
-        }
-        prev_cost = cost;
     }
-    prev_cost
+    Ok(())
 }
 
 fn main() {
-    let mut crabs = parse_crabs();
-    crabs.sort();
-    println!("Crabs: {:?}", crabs);
-    println!("Part 1: {}", part1(crabs.as_slice()));
-    println!("Part 2: {}", part2(crabs.as_slice()));
+    // `--curve` exports the fuel cost curves instead of the answers.
+    let mut args = ExtraArgs::from_env::<Day7>("[--curve]");
+    if args.take_switch("--curve") {
+        args.run_mode(curve);
+    } else {
+        args.run_answers::<Day7>();
+    }
 }
@@ -0,0 +1,79 @@
+use aoc_common::{Answer, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Signal {
+    digits: String,
+}
+
+impl Signal {
+    fn len(&self) -> usize {
+        self.digits.len()
+    }
+}
+
+fn parse_signals_part(input: &str, val: &str) -> Result<Vec<Signal>, ParseError> {
+    let mut signals: Vec<Signal> = Vec::new();
+    for seg in val.split_whitespace() {
+        if !seg.chars().all(|c| ('a'..='g').contains(&c)) {
+            return Err(ParseError::at(input, seg, "Invalid segments"));
+        }
+        let mut seg_str: String = String::from(seg);
+        let mut chars: Vec<char> = seg_str.chars().collect();
+        chars.sort_by(|a, b| b.cmp(a));
+        seg_str = String::from_iter(chars);
+        signals.push(Signal { digits: seg_str });
+    }
+    Ok(signals)
+}
+
+fn parse_signals(input: &str) -> Result<Vec<(Vec<Signal>, Vec<Signal>)>, ParseError> {
+    let mut out: Vec<(Vec<Signal>, Vec<Signal>)> = Vec::new();
+    for line in aoc_common::lines(input) {
+        let buffer_parts: Vec<&str> = line.split(" | ").collect();
+        if buffer_parts.len() != 2 {
+            return Err(ParseError::at(
+                input,
+                line,
+                "Expected patterns and outputs separated by \" | \"",
+            ));
+        }
+        out.push((
+            parse_signals_part(input, buffer_parts[0])?,
+            parse_signals_part(input, buffer_parts[1])?,
+        ));
+    }
+    Ok(out)
+}
+
+fn part1(signals: &[(Vec<Signal>, Vec<Signal>)]) -> usize {
+    let mut count: usize = 0;
+    for (_, out_sig) in signals {
+        for sig in out_sig {
+            let n = sig.len();
+            if n == 2 || n == 4 || n == 3 || n == 7 {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Vec<(Vec<Signal>, Vec<Signal>)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_signals(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(part1(input.as_slice()).to_string())
+    }
+
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        // Part 2 was only solved in part2.py.
+        None
+    }
+}
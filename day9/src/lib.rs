@@ -0,0 +1,69 @@
+use aoc_common::{Answer, Grid, ParseError, Solution};
+use std::collections::HashSet;
+use std::collections::LinkedList;
+
+fn find_low_points(heightmap: &Grid<u8>) -> Vec<(usize, usize)> {
+    heightmap
+        .positions()
+        .filter(|pos| {
+            heightmap
+                .neighbors4(*pos)
+                .all(|other| heightmap[other] > heightmap[*pos])
+        })
+        .collect()
+}
+
+fn part1(heightmap: &Grid<u8>) -> u64 {
+    let mut out: u64 = 0;
+    for pos in find_low_points(heightmap) {
+        out += heightmap[pos] as u64 + 1;
+    }
+    out
+}
+
+fn part2(heightmap: &Grid<u8>) -> usize {
+    let mut basin_sizes: Vec<usize> = Vec::new();
+    for low in find_low_points(heightmap) {
+        let mut stack: LinkedList<(usize, usize)> = LinkedList::new();
+        let mut basin: HashSet<(usize, usize)> = HashSet::new();
+        basin.insert(low);
+        stack.push_back(low);
+        while !stack.is_empty() {
+            let pos = stack.pop_back().expect("Empty stack");
+            for pt in heightmap.neighbors4(pos) {
+                let val = heightmap[pt];
+                let source = heightmap[pos];
+                if val >= source && val != 9 && !basin.contains(&pt) {
+                    basin.insert(pt);
+                    stack.push_back(pt);
+                }
+            }
+        }
+        basin_sizes.push(basin.len());
+    }
+    basin_sizes.sort();
+    basin_sizes[basin_sizes.len() - 3..]
+        .iter()
+        .copied()
+        .reduce(|x, y| x * y)
+        .expect("No values")
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_digits(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Ok(part1(input).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Ok(part2(input).to_string()))
+    }
+}